@@ -0,0 +1,108 @@
+use std::cmp;
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+use rs_semver::Version;
+
+const VERSIONS: usize = 100_000;
+const ROUNDS: usize = 10;
+
+/// Produces versions that mostly differ in their pre-release tags, which is what makes sorting them expensive.
+fn versions() -> Vec<Version> {
+    let tags = ["alpha", "beta", "rc", "next", "canary.20240101"];
+    let mut seed = 0x2545_f491_u32;
+
+    (0..VERSIONS)
+        .map(|_| {
+            seed ^= seed << 13;
+            seed ^= seed >> 17;
+            seed ^= seed << 5;
+
+            let tag = tags[(seed % tags.len() as u32) as usize];
+            let s = format!("1.{}.0-{}.{}.{}", seed % 4, tag, seed % 50, seed % 7);
+
+            Version::from(&s).unwrap()
+        })
+        .collect()
+}
+
+/// A version that keeps its pre-release tag as a single string, the way `VersionPreRelease` used to,
+/// so that every comparison has to split and parse the tags again.
+#[derive(Clone, Eq, PartialEq)]
+struct StringTagged((u64, u64, u64), String);
+
+impl From<&Version> for StringTagged {
+    fn from(ver: &Version) -> Self {
+        let tag = ver.pre_release.as_ref().unwrap().to_string();
+
+        Self(
+            (ver.core.major, ver.core.minor, ver.core.patch),
+            tag[1..].to_string(),
+        )
+    }
+}
+
+impl Ord for StringTagged {
+    fn cmp(&self, other: &Self) -> cmp::Ordering {
+        use cmp::Ordering::*;
+
+        let num = |part: &str| {
+            part.bytes()
+                .all(|b| b.is_ascii_digit())
+                .then(|| part.parse::<u64>().ok())
+                .flatten()
+        };
+
+        self.0.cmp(&other.0).then_with(|| {
+            for (part, other_part) in self.1.split('.').zip(other.1.split('.')) {
+                let ord = match (num(part), num(other_part)) {
+                    (Some(_), None) => Less,
+                    (None, Some(_)) => Greater,
+                    (Some(id), Some(other_id)) => id.cmp(&other_id),
+                    _ => part.cmp(other_part),
+                };
+
+                if let Less | Greater = ord {
+                    return ord;
+                }
+            }
+
+            self.1.split('.').count().cmp(&other.1.split('.').count())
+        })
+    }
+}
+
+impl PartialOrd for StringTagged {
+    fn partial_cmp(&self, other: &Self) -> Option<cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+fn time_sorting<T: Clone + Ord>(versions: Vec<T>) -> Duration {
+    let mut rounds = vec![versions; ROUNDS];
+    let start = Instant::now();
+
+    for round in &mut rounds {
+        round.sort();
+    }
+
+    black_box(rounds);
+
+    start.elapsed() / ROUNDS as u32
+}
+
+fn main() {
+    let versions = versions();
+    let string_tagged = versions.iter().map(StringTagged::from).collect();
+
+    println!(
+        "sorting {} pre-release versions with string tags: {:?} per round",
+        VERSIONS,
+        time_sorting::<StringTagged>(string_tagged)
+    );
+    println!(
+        "sorting {} pre-release versions with typed identifiers: {:?} per round",
+        VERSIONS,
+        time_sorting(versions)
+    );
+}
@@ -1,6 +1,22 @@
+pub use range::bound::RangeBound;
+pub use range::canonical::CanonicalRange;
+pub use range::comparator::RangeComparator;
+pub use range::error::{RangeParseError, RangeParseErrorKind};
 pub use range::matcher::MatchingAlg;
+pub use range::position::RangePosition;
+pub use range::selection::Preference;
+pub use range::set::VersionSet;
+pub use range::sugar::RangeSugar;
+pub use range::unit::RangeUnit;
 pub use range::Range;
-pub use version::difference::VersionDiff;
+pub use version::build::VersionBuild;
+pub use version::builder::VersionBuilder;
+pub use version::coerce::CoerceOptions;
+pub use version::core::VersionCore;
+pub use version::difference::{IdentifierBase, IncrementOptions, VersionDiff};
+pub use version::error::{VersionIncrementError, VersionParseError, VersionParseErrorKind};
+pub use version::mode::ParseMode;
+pub use version::pre_release::{PreReleaseIdentifier, VersionPreRelease};
 pub use version::Version;
 
 mod range;
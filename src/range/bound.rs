@@ -3,8 +3,19 @@ use std::fmt;
 use super::comparator::RangeComparator;
 use crate::version::Version;
 
+/// A version along with the comparator that it's matched with, e.g. `>=1.2.3`.
 #[derive(Clone, Debug, PartialEq)]
-pub(crate) struct RangeBound(pub(crate) RangeComparator, pub(crate) Version);
+pub struct RangeBound(pub(crate) RangeComparator, pub(crate) Version);
+
+impl RangeBound {
+    pub fn comparator(&self) -> RangeComparator {
+        self.0
+    }
+
+    pub fn version(&self) -> &Version {
+        &self.1
+    }
+}
 
 impl fmt::Display for RangeBound {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
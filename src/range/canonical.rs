@@ -0,0 +1,96 @@
+use std::{fmt, hash};
+
+use super::matcher::MatchingAlg;
+use super::set::VersionSet;
+use super::Range;
+
+/// A range that's compared and hashed by the versions it matches with either `MatchingAlg`, rather than by its units,
+/// so e.g. `^1.2.3` equals `>=1.2.3 <2.0.0-0`, and `>=1.0.0 <2.0.0-0 || >=1.5.0` equals `>=1.0.0`.
+///
+/// It's built with `Range::canonicalize`, and is meant to be used as a key in maps and sets.
+#[derive(Clone, Debug)]
+pub struct CanonicalRange {
+    range: Range,
+    classic: VersionSet,
+    node: VersionSet,
+}
+
+impl CanonicalRange {
+    /// The normalized range (see `Range::normalize`); equal canonical ranges may still hold different ones.
+    pub fn range(&self) -> &Range {
+        &self.range
+    }
+}
+
+impl PartialEq for CanonicalRange {
+    fn eq(&self, other: &Self) -> bool {
+        self.classic == other.classic && self.node == other.node
+    }
+}
+
+impl Eq for CanonicalRange {}
+
+impl hash::Hash for CanonicalRange {
+    fn hash<H: hash::Hasher>(&self, state: &mut H) {
+        self.classic.hash(state);
+        self.node.hash(state);
+    }
+}
+
+impl fmt::Display for CanonicalRange {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.range)
+    }
+}
+
+impl Range {
+    /// Wraps the normalized range into a `CanonicalRange`, which equals the canonical form of any other range that
+    /// matches the same versions with either `MatchingAlg`.
+    pub fn canonicalize(&self) -> CanonicalRange {
+        CanonicalRange {
+            range: self.normalize(),
+            classic: self.to_version_set(MatchingAlg::Classic),
+            node: self.to_version_set(MatchingAlg::Node),
+        }
+    }
+}
+
+#[test]
+fn test_canonicalize() {
+    use std::collections::HashSet;
+
+    let test = |a, b| {
+        let (a, b) = (
+            Range::from(a).unwrap().canonicalize(),
+            Range::from(b).unwrap().canonicalize(),
+        );
+
+        (a == b, HashSet::from([a, b]).len() == 1)
+    };
+
+    assert_eq!((true, true), test("^1.2.3", ">=1.2.3 <2.0.0-0"));
+    assert_eq!((true, true), test(">=1.0.0 <2.0.0-0 || >=1.5.0", ">=1.0.0"));
+    assert_eq!(
+        (true, true),
+        test("1.x || 2.x", "^2.0.0 || <2.0.0-0 >=1.0.0 || 1.5.x")
+    );
+    assert_eq!((true, true), test(">=2.0.0 <1.0.0", "1.2.3 1.2.4"));
+    assert_eq!((true, true), test("1.2.3", ">=1.2.3 <=1.2.3"));
+    assert_eq!(
+        (true, true),
+        test("1.2.3 - 2.0.0", ">=1.2.3 <2.0.0 || 2.0.0")
+    );
+    // the pre-release versions of `2.0.0` are only matched by the latter with `MatchingAlg::Classic`
+    assert_eq!((false, false), test("^1.2.3", ">=1.2.3 <2.0.0"));
+    // the pre-release versions of `1.0.0` are only matched by the former with `MatchingAlg::Node`
+    assert_eq!((false, false), test(">1.0.0-rc", ">1.0.0-rc >=0.0.0"));
+    assert_eq!((false, false), test("^1.2.3", "^1.2.4"));
+}
+
+#[test]
+fn test_canonicalize_matches() {
+    super::testing::assert_for_range_pairs(
+        |a, b, _| a.canonicalize() == b.canonicalize(),
+        |&is_equal, _, _, in_a, in_b| !is_equal || in_a == in_b,
+    );
+}
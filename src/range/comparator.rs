@@ -1,7 +1,8 @@
 use std::fmt;
 
-#[derive(Clone, Debug, PartialEq)]
-pub(crate) enum RangeComparator {
+/// The comparison that a `RangeBound` applies to versions; `Equal` is written without a prefix.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum RangeComparator {
     Less,
     LessOrEqual,
     Equal,
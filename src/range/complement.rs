@@ -0,0 +1,147 @@
+use super::bound::RangeBound;
+use super::comparator::RangeComparator;
+use super::group;
+use super::interval::{lowest_version, Interval};
+use super::unit::RangeUnit;
+use super::Range;
+
+impl RangeBound {
+    /// Returns the bound that matches exactly the versions this one doesn't (`>=v` becomes `<v`, etc.).
+    ///
+    /// `Equal` bounds are expected to be turned into `LessOrEqual` or `GreaterOrEqual` beforehand.
+    fn to_flipped(&self) -> Self {
+        use RangeComparator::*;
+
+        let comp = match self.0 {
+            Less => GreaterOrEqual,
+            LessOrEqual => Greater,
+            Equal | GreaterOrEqual => Less,
+            Greater => LessOrEqual,
+        };
+
+        Self(comp, self.1.clone())
+    }
+}
+
+impl Range {
+    /// Finds the range of versions that aren't matched by `self`.
+    ///
+    /// The result is made of the gaps between the unit groups, each one starting and ending at the flipped bound of
+    /// its neighbour (e.g. `>=1.2.0 <1.4.5 || >=2.0.0 <2.1.3` becomes `<1.2.0 || >=1.4.5 <2.0.0 || >=2.1.3`).
+    /// If `self` matches every version, then the result is `<0.0.0-0`.
+    ///
+    /// It's exact with `MatchingAlg::Classic`, but only for versions without pre-release tags with `MatchingAlg::Node`:
+    /// a unit group can't match pre-release versions of more than one version core in that case, so there's no way to
+    /// express e.g. "all pre-release versions except `1.2.3-rc.1`" as a range.
+    pub fn complement(&self) -> Self {
+        let mut unit_groups = self
+            .unit_groups()
+            .into_iter()
+            .filter_map(group::normalize)
+            .collect::<Vec<_>>();
+
+        unit_groups.sort_by_cached_key(|units| group::to_interval(units));
+
+        // merge the groups into disjoint intervals, remembering the bounds they start and end at
+        let mut pieces = Vec::<(Interval, Option<RangeBound>, Option<RangeBound>)>::new();
+
+        for units in &unit_groups {
+            let interval = group::to_interval(units);
+            let lower = match interval.start == lowest_version() {
+                true => None,
+                false => group::lower_bound(units),
+            };
+            let upper = interval
+                .end
+                .as_ref()
+                .and_then(|_| group::upper_bound(units));
+
+            if let Some((last, _, last_upper)) = pieces.last_mut() {
+                if let Some(hull) = last.hull(&interval) {
+                    if hull.end != last.end {
+                        *last_upper = upper;
+                    }
+
+                    *last = hull;
+                    continue;
+                }
+            }
+
+            pieces.push((interval, lower, upper));
+        }
+
+        let mut gaps = vec![];
+        // `Some(None)` stands for a gap that starts at the lowest version, and `None` for no gap at all
+        let mut gap_start = Some(None::<RangeBound>);
+
+        for (_, lower, upper) in pieces {
+            if let (Some(start), Some(lower)) = (&gap_start, lower) {
+                let end = lower.to_flipped();
+
+                gaps.push(match start {
+                    Some(start) => vec![RangeUnit::new(start.clone(), Some(end))],
+                    None => vec![RangeUnit::new(end, None)],
+                });
+            }
+
+            gap_start = upper.map(|upper| Some(upper.to_flipped()));
+        }
+
+        match gap_start {
+            Some(Some(start)) => gaps.push(vec![RangeUnit::new(start, None)]),
+            Some(None) => gaps.push(vec![RangeUnit::new(
+                RangeBound(RangeComparator::GreaterOrEqual, lowest_version()),
+                None,
+            )]),
+            None => {}
+        }
+
+        Self::from_groups(gaps)
+    }
+}
+
+#[test]
+fn test_complement() {
+    let test = |s| Range::from(s).unwrap().complement().to_string();
+
+    assert_eq!(
+        "<1.2.0 || >=1.4.5 <2.0.0 || >=2.1.3",
+        test(">=1.2.0 <1.4.5 || >=2.0.0 <2.1.3")
+    );
+    assert_eq!("<1.2.3 || >=2.0.0-0", test("^1.2.3"));
+    assert_eq!("<1.2.3 || >1.2.3", test("1.2.3"));
+    assert_eq!("<1.2.3 || >1.2.3 <1.2.4 || >1.2.4", test("1.2.4 || 1.2.3"));
+    assert_eq!("<=1.2.3", test(">1.2.3"));
+    assert_eq!(">=1.2.3-rc", test("<1.2.3-rc"));
+    assert_eq!("<0.0.0", test("*"));
+    assert_eq!("<0.0.0-0", test(">=0.0.0-0"));
+    assert_eq!("<0.0.0-0", test("<1.0.0 || >=1.0.0"));
+    assert_eq!(">=0.0.0-0", test("<0.0.0-0"));
+    assert_eq!(">=0.0.0-0", test(">=2.0.0 <1.0.0"));
+    assert_eq!(
+        "<1.0.0 || >=3.0.0",
+        test(">=1.0.0 <2.0.0 || >=1.5.0 <3.0.0")
+    );
+}
+
+#[test]
+fn test_complement_matches() {
+    use super::matcher::MatchingAlg::*;
+
+    super::testing::assert_for_ranges(
+        |range, _| {
+            let complement = range.complement();
+
+            assert_eq!(
+                complement.to_string(),
+                Range::from(&complement.to_string()).unwrap().to_string()
+            );
+            complement
+        },
+        // with `Node`, the complement is only exact for versions without pre-release tags
+        |complement, alg, ver, matched| {
+            (alg == Node && ver.pre_release.is_some())
+                || complement.is_matched_by(alg, ver) != matched
+        },
+    );
+}
@@ -0,0 +1,68 @@
+use super::group;
+use super::unit::RangeUnit;
+use super::Range;
+
+impl Range {
+    /// Checks whether the range can't match any version, i.e. every unit group has conflicting units
+    /// (e.g. `>2.0.0 <1.0.0` or `1.2.3 4.5.6`).
+    ///
+    /// This is decided with `MatchingAlg::Classic`, which matches every version `MatchingAlg::Node` does.
+    /// To also detect ranges like `>1.2.3 <1.2.4` that only match pre-release versions which `MatchingAlg::Node`
+    /// skips, check that `min_version` returns `None` instead.
+    pub fn is_empty(&self) -> bool {
+        self.unit_groups()
+            .into_iter()
+            .all(|units| group::to_interval(units).is_empty())
+    }
+
+    /// Lists a pair of conflicting units for every unit group that can't match any version, see `is_empty`.
+    ///
+    /// A unit that can't match anything on its own (e.g. `<0.0.0-0`) is paired with itself.
+    pub fn conflicts(&self) -> Vec<(&RangeUnit, &RangeUnit)> {
+        self.unit_groups()
+            .into_iter()
+            .filter_map(|units| {
+                group::find_conflict(units).map(|(idx, other_idx)| (&units[idx], &units[other_idx]))
+            })
+            .collect()
+    }
+}
+
+#[test]
+fn test_is_empty() {
+    let test = |s| Range::from(s).unwrap().is_empty();
+
+    assert!(test(">2.0.0 <1.0.0"));
+    assert!(test("1.2.3 4.5.6"));
+    assert!(test("1.2.3 4.5.6 7.8.9"));
+    assert!(test("<0.0.0-0"));
+    assert!(test(">=2.0.0 <1.0.0 || 1.2.3 1.2.4"));
+    assert!(!test("<0.0.0"));
+    assert!(!test(">1.2.3 <1.2.4"));
+    assert!(!test(">2.0.0 <1.0.0 || ^1.2.3"));
+    assert!(!test("*"));
+}
+
+#[test]
+fn test_conflicts() {
+    let test = |s| {
+        Range::from(s)
+            .unwrap()
+            .conflicts()
+            .into_iter()
+            .map(|(a, b)| (a.to_string(), b.to_string()))
+            .collect::<Vec<_>>()
+    };
+    let pair = |a: &str, b: &str| (a.to_string(), b.to_string());
+
+    assert_eq!(vec![pair(">2.0.0", "<1.0.0")], test(">2.0.0 <1.0.0"));
+    assert_eq!(
+        vec![pair("1.2.3", "4.5.6"), pair("<0.0.0-0", "<0.0.0-0")],
+        test(">=1.0.0 1.2.3 4.5.6 || ^2.0.0 || <0.0.0-0")
+    );
+    assert_eq!(
+        vec![pair(">=1.2.3 <2.0.0-0", "<1.0.0")],
+        test("^1.2.3 <1.0.0")
+    );
+    assert!(test("^1.2.3 || >=2.0.0").is_empty());
+}
@@ -0,0 +1,149 @@
+use super::bound::RangeBound;
+use super::comparator::RangeComparator;
+use super::sugar::RangeSugar;
+use super::unit::{ParsedComparator, RangeUnit};
+use super::Range;
+use crate::version::Version;
+
+impl Range {
+    /// Builds `=ver`, the same as parsing `1.2.3`; build metadata is dropped.
+    pub fn exact(ver: Version) -> Self {
+        Self::Just(RangeUnit::from_version(None, ver))
+    }
+
+    /// Builds `^ver`, e.g. `>=1.2.3 <2.0.0-0` for `1.2.3`.
+    pub fn caret(ver: Version) -> Self {
+        Self::Just(RangeUnit::from_version(Some(ParsedComparator::Caret), ver))
+    }
+
+    /// Builds `~ver`, e.g. `>=1.2.3 <1.3.0-0` for `1.2.3`.
+    pub fn tilde(ver: Version) -> Self {
+        Self::Just(RangeUnit::from_version(Some(ParsedComparator::Tilde), ver))
+    }
+
+    /// Builds `>=ver`.
+    pub fn at_least(ver: Version) -> Self {
+        Self::Just(RangeUnit::from_version(
+            Some(ParsedComparator::Simple(RangeComparator::GreaterOrEqual)),
+            ver,
+        ))
+    }
+
+    /// Builds `<ver`.
+    pub fn below(ver: Version) -> Self {
+        Self::Just(RangeUnit::from_version(
+            Some(ParsedComparator::Simple(RangeComparator::Less)),
+            ver,
+        ))
+    }
+
+    /// Builds `*`, which matches every version without a pre-release tag (and all of them with `MatchingAlg::Classic`).
+    pub fn any() -> Self {
+        Self::Just(
+            RangeUnit::new(
+                RangeBound(RangeComparator::GreaterOrEqual, Version::new(0, 0, 0)),
+                None,
+            )
+            .with_sugar(RangeSugar::XRange),
+        )
+    }
+
+    /// Joins two ranges with AND (i.e. a space), so that a version has to match both of them.
+    ///
+    /// If either range has multiple unit groups, then every group of `self` is joined with every group of `other`,
+    /// e.g. `1.x || 3.x` and `>=1.5.0` become `1.x >=1.5.0 || 3.x >=1.5.0`.
+    /// Unlike `intersect`, the result isn't normalized.
+    pub fn and(self, other: Self) -> Self {
+        let other_groups = other.into_unit_groups();
+        let unit_groups = self
+            .into_unit_groups()
+            .into_iter()
+            .flat_map(|units| {
+                other_groups
+                    .iter()
+                    .map(move |other_units| [units.as_slice(), other_units].concat())
+            })
+            .collect();
+
+        Self::from_groups(unit_groups)
+    }
+
+    /// Joins two ranges with OR (i.e. `||`), so that a version has to match either of them.
+    ///
+    /// Unlike `union`, the result isn't normalized.
+    pub fn or(self, other: Self) -> Self {
+        let mut unit_groups = self.into_unit_groups();
+        unit_groups.extend(other.into_unit_groups());
+
+        Self::from_groups(unit_groups)
+    }
+
+    fn into_unit_groups(self) -> Vec<Vec<RangeUnit>> {
+        match self {
+            Self::Just(unit) => vec![vec![unit]],
+            Self::All(units) => vec![units],
+            Self::Any(unit_groups) => unit_groups,
+        }
+    }
+}
+
+#[test]
+fn test_constructors() {
+    let test = |range: Range, s| {
+        let parsed = Range::from(s).unwrap();
+
+        assert_eq!(parsed, range);
+        assert_eq!(parsed.to_sugared_string(), range.to_sugared_string());
+    };
+    let ver = || Version::from("1.2.3-rc.1+build").unwrap();
+
+    test(Range::exact(ver()), "1.2.3-rc.1");
+    test(Range::caret(ver()), "^1.2.3-rc.1");
+    test(Range::tilde(ver()), "~1.2.3-rc.1");
+    test(Range::at_least(ver()), ">=1.2.3-rc.1");
+    test(Range::below(ver()), "<1.2.3-rc.1");
+    test(Range::any(), "*");
+    test(Range::caret(Version::new(0, 0, 1)), "^0.0.1");
+}
+
+#[test]
+fn test_combinators() {
+    let test = |range: Range, s| {
+        assert_eq!(Range::from(s).unwrap(), range);
+        assert_eq!(s, range.to_sugared_string());
+    };
+    let ver = Version::new;
+
+    test(
+        Range::caret(ver(1, 2, 3)).and(Range::below(ver(1, 5, 0))),
+        "^1.2.3 <1.5.0",
+    );
+    test(
+        Range::at_least(ver(1, 0, 0))
+            .and(Range::below(ver(2, 0, 0)))
+            .and(Range::exact(ver(1, 2, 3))),
+        ">=1.0.0 <2.0.0 1.2.3",
+    );
+    test(
+        Range::exact(ver(1, 2, 3)).or(Range::exact(ver(1, 2, 4))),
+        "1.2.3 || 1.2.4",
+    );
+    test(
+        Range::caret(ver(1, 0, 0))
+            .or(Range::caret(ver(3, 0, 0)))
+            .and(Range::at_least(ver(1, 5, 0))),
+        "^1.0.0 >=1.5.0 || ^3.0.0 >=1.5.0",
+    );
+    test(
+        Range::tilde(ver(1, 2, 3))
+            .and(Range::below(ver(1, 2, 5)))
+            .or(Range::any()),
+        "~1.2.3 <1.2.5 || *",
+    );
+    test(
+        Range::exact(ver(1, 0, 0))
+            .or(Range::exact(ver(2, 0, 0)))
+            .and(Range::exact(ver(3, 0, 0)).or(Range::exact(ver(4, 0, 0)))),
+        "1.0.0 3.0.0 || 1.0.0 4.0.0 || 2.0.0 3.0.0 || 2.0.0 4.0.0",
+    );
+}
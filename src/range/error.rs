@@ -0,0 +1,168 @@
+use std::{error, fmt, ops};
+
+use crate::version::error::{VersionParseError, VersionParseErrorKind};
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum RangeParseErrorKind {
+    Empty,
+    DanglingOr,
+    InvalidUnit,
+    ComparatorBeforeHyphen,
+    PreReleaseOnPattern,
+    ComparatorBeforeWildcard,
+    Unsatisfiable,
+}
+
+impl fmt::Display for RangeParseErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Empty => write!(f, "empty range"),
+            Self::DanglingOr => write!(f, "`||` must be followed by a range"),
+            Self::InvalidUnit => write!(f, "invalid range unit"),
+            Self::ComparatorBeforeHyphen => {
+                write!(f, "comparator not allowed before hyphen range")
+            }
+            Self::PreReleaseOnPattern => {
+                write!(f, "pre-release tag not allowed after partial version")
+            }
+            Self::ComparatorBeforeWildcard => write!(f, "comparator not allowed before `*`"),
+            Self::Unsatisfiable => write!(f, "conflicting units can't match any version"),
+        }
+    }
+}
+
+/// Describes a `RangeUnit` that failed to parse.
+///
+/// `rest` starts at the offending character if `cause` is present (i.e. the version itself is malformed),
+/// or right after the unit otherwise.
+#[derive(Debug, PartialEq)]
+pub(crate) struct UnitParseError<'a> {
+    pub(crate) kind: RangeParseErrorKind,
+    pub(crate) rest: &'a str,
+    pub(crate) cause: Option<VersionParseErrorKind>,
+}
+
+impl<'a> UnitParseError<'a> {
+    pub(crate) fn new(kind: RangeParseErrorKind, rest: &'a str) -> Self {
+        Self {
+            kind,
+            rest,
+            cause: None,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RangeParseError {
+    input: String,
+    span: ops::Range<usize>,
+    kind: RangeParseErrorKind,
+    version_error: Option<VersionParseError>,
+}
+
+impl RangeParseError {
+    /// Builds an error for `input` that covers `len` bytes starting where `rest` starts.
+    pub(crate) fn new(input: &str, rest: &str, len: usize, kind: RangeParseErrorKind) -> Self {
+        let start = input.len() - rest.len();
+
+        Self {
+            input: input.to_string(),
+            span: start..(start + len),
+            kind,
+            version_error: None,
+        }
+    }
+
+    /// Builds an error for `input` from a failure to parse the unit that starts where `unit` starts.
+    pub(crate) fn from_unit(input: &str, unit: &str, err: UnitParseError) -> Self {
+        let start = input.len() - unit.len();
+        let failed_at = input.len() - err.rest.len();
+        let end = match err.cause {
+            Some(_) => failed_at + err.rest.find([' ', '|']).unwrap_or(err.rest.len()),
+            None => failed_at,
+        };
+
+        let unit = &input[start..end];
+        let version_error = err
+            .cause
+            .map(|kind| VersionParseError::new(unit, &unit[(failed_at - start)..], kind));
+
+        Self {
+            input: input.to_string(),
+            span: start..end,
+            kind: err.kind,
+            version_error,
+        }
+    }
+
+    /// The string that was being parsed.
+    pub fn input(&self) -> &str {
+        &self.input
+    }
+
+    /// Byte range of `input` that failed to parse, usually a single range unit (e.g. `^1.2-rc`).
+    pub fn span(&self) -> ops::Range<usize> {
+        self.span.clone()
+    }
+
+    pub fn kind(&self) -> RangeParseErrorKind {
+        self.kind
+    }
+
+    /// The underlying error if the unit contains a malformed version, with offsets relative to the start of the unit.
+    pub fn version_error(&self) -> Option<&VersionParseError> {
+        self.version_error.as_ref()
+    }
+}
+
+impl fmt::Display for RangeParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let column = self.input[..self.span.start].chars().count();
+        let width = self.input[self.span.clone()].chars().count().max(1);
+
+        write!(f, "{}", self.kind)?;
+
+        if let Some(err) = &self.version_error {
+            write!(f, " ({})", err.kind())?;
+        }
+
+        writeln!(f, " at {}..{}", self.span.start, self.span.end)?;
+        writeln!(f, "  {}", self.input)?;
+        write!(f, "  {}{}", " ".repeat(column), "^".repeat(width))
+    }
+}
+
+impl error::Error for RangeParseError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        self.version_error
+            .as_ref()
+            .map(|err| err as &(dyn error::Error + 'static))
+    }
+}
+
+#[test]
+fn test_to_string() {
+    use RangeParseErrorKind::*;
+
+    assert_eq!(
+        "pre-release tag not allowed after partial version at 5..12\n  1 || ^1.2-rc\n       ^^^^^^^",
+        RangeParseError::new("1 || ^1.2-rc", "^1.2-rc", 7, PreReleaseOnPattern).to_string()
+    );
+    assert_eq!(
+        "`||` must be followed by a range at 6..8\n  1.2.3 ||\n        ^^",
+        RangeParseError::new("1.2.3 ||", "||", 2, DanglingOr).to_string()
+    );
+    assert_eq!(
+        "invalid range unit (numeric identifier has a leading zero) at 3..8\n  >1 <1.02\n     ^^^^^",
+        RangeParseError::from_unit(
+            ">1 <1.02",
+            "<1.02",
+            UnitParseError {
+                kind: InvalidUnit,
+                rest: "02",
+                cause: Some(VersionParseErrorKind::LeadingZero),
+            }
+        )
+        .to_string()
+    );
+}
@@ -0,0 +1,113 @@
+use super::bound::RangeBound;
+use super::comparator::RangeComparator;
+use super::group;
+use super::interval::Interval;
+use super::matcher::MatchingAlg;
+use super::set::VersionSet;
+use super::Range;
+use crate::version::Version;
+
+impl Range {
+    /// Finds the smallest version matched by the range with the given `alg`, following `node-semver`'s `minVersion`
+    /// (e.g. `>1.2.3` starts at `1.2.4-0` with `MatchingAlg::Classic`, but at `1.2.4` with `MatchingAlg::Node`).
+    ///
+    /// Returns `None` if the range doesn't match anything.
+    pub fn min_version(&self, alg: MatchingAlg) -> Option<Version> {
+        VersionSet::new(self, alg).first().cloned()
+    }
+
+    /// Finds the largest version matched by the range with the given `alg`, as long as the range ends at an inclusive
+    /// bound (e.g. `<=1.2.3` or `1.2.3 - 1.4.0`).
+    ///
+    /// Returns `None` if the range has no upper limit, ends at an exclusive bound (there's no largest version below
+    /// `<2.0.0-0`, since pre-release tags can be arbitrarily long), or doesn't match anything.
+    pub fn max_version(&self, alg: MatchingAlg) -> Option<Version> {
+        let ver = self
+            .unit_groups()
+            .into_iter()
+            .flat_map(group::bounds)
+            .filter(|RangeBound(comp, _)| {
+                matches!(comp, RangeComparator::LessOrEqual | RangeComparator::Equal)
+            })
+            .map(|RangeBound(_, ver)| ver)
+            .filter(|ver| self.is_matched_by(alg, ver))
+            .max()?;
+
+        let above = Interval::from_bound(&RangeBound(RangeComparator::Greater, ver.clone()));
+
+        (!VersionSet::new(self, alg).intersects_interval(&above)).then(|| ver.clone())
+    }
+}
+
+#[test]
+fn test_min_version() {
+    use MatchingAlg::*;
+
+    let test = |s, alg| {
+        Range::from(s)
+            .unwrap()
+            .min_version(alg)
+            .map(|v| v.to_string())
+    };
+
+    for alg in [Classic, Node] {
+        assert_eq!(Some("0.0.1".to_string()), test("^0.0.1", alg));
+        assert_eq!(Some("0.0.0".to_string()), test("*", alg));
+        assert_eq!(Some("1.2.3".to_string()), test("1.2.3 || ^2.0.0", alg));
+        assert_eq!(Some("1.2.0".to_string()), test("^2.0.0 || ~1.2", alg));
+        assert_eq!(Some("1.2.3-rc".to_string()), test(">=1.2.3-rc", alg));
+        assert_eq!(None, test(">=2.0.0 <1.0.0", alg));
+        assert_eq!(None, test("<0.0.0-0", alg));
+    }
+
+    assert_eq!(Some("1.2.4-0".to_string()), test(">1.2.3", Classic));
+    assert_eq!(Some("1.2.4".to_string()), test(">1.2.3", Node));
+    assert_eq!(Some("0.0.0-0".to_string()), test("<1.0.0", Classic));
+    assert_eq!(Some("0.0.0".to_string()), test("<1.0.0", Node));
+    assert_eq!(Some("1.2.3-rc.0".to_string()), test(">1.2.3-rc", Classic));
+    assert_eq!(Some("1.2.3-rc.0".to_string()), test(">1.2.3-rc", Node));
+    // with `Node`, pre-release versions aren't matched due to `>=1.0.0`
+    assert_eq!(
+        Some("1.2.3-alpha".to_string()),
+        test(">=1.0.0 >=1.2.3-alpha <1.2.4", Classic)
+    );
+    assert_eq!(
+        Some("1.2.3".to_string()),
+        test(">=1.0.0 >=1.2.3-alpha <1.2.4", Node)
+    );
+}
+
+#[test]
+fn test_max_version() {
+    use MatchingAlg::*;
+
+    let test = |s, alg| {
+        Range::from(s)
+            .unwrap()
+            .max_version(alg)
+            .map(|v| v.to_string())
+    };
+
+    for alg in [Classic, Node] {
+        assert_eq!(Some("1.2.3".to_string()), test("<=1.2.3", alg));
+        assert_eq!(Some("1.2.3".to_string()), test("1.2.3", alg));
+        assert_eq!(Some("4.5.6".to_string()), test("1.2.3 - 4.5.6", alg));
+        assert_eq!(Some("2.0.0".to_string()), test("^1.2.3 || 2.0.0", alg));
+        assert_eq!(Some("1.2.3".to_string()), test("1.2.3 || <=1.0.0", alg));
+        assert_eq!(None, test("1.2.3 || >=2.0.0", alg));
+        assert_eq!(None, test("<=1.2.3 || ^1.2.3", alg));
+        assert_eq!(None, test("^1.2.3", alg));
+        assert_eq!(None, test("*", alg));
+        assert_eq!(None, test(">=2.0.0 <=1.0.0", alg));
+    }
+
+    assert_eq!(Some("1.2.3-rc".to_string()), test("<=1.2.3-rc", Node));
+    // with `Node`, `1.2.3-rc` isn't matched due to `>=1.0.0`
+    assert_eq!(
+        Some("1.2.3-rc".to_string()),
+        test(">=1.0.0 <=1.2.3-rc", Classic)
+    );
+    assert_eq!(None, test(">=1.0.0 <=1.2.3-rc", Node));
+    // `1.3.0-alpha` is above `1.2.3-rc`, even though `>1.2.3-rc` doesn't match it with `Node`
+    assert_eq!(None, test("<=1.2.3-rc || >=1.3.0-alpha <1.3.0-beta", Node));
+}
@@ -0,0 +1,360 @@
+use super::bound::RangeBound;
+use super::comparator::RangeComparator;
+use super::interval::{lowest_version, successor, Interval};
+use super::unit::RangeUnit;
+use crate::version::core::VersionCore;
+
+pub(crate) fn bounds(units: &[RangeUnit]) -> impl Iterator<Item = &RangeBound> + Clone {
+    units
+        .iter()
+        .flat_map(|unit| std::iter::once(&unit.bound).chain(unit.extra_bound.as_ref()))
+}
+
+/// Returns the version core shared by all of `bounds` if every one of them has a pre-release tag.
+///
+/// With `MatchingAlg::Node`, a group of bounds can only match pre-release versions of that core.
+pub(crate) fn pre_release_core<'a>(
+    mut bounds: impl Iterator<Item = &'a RangeBound>,
+) -> Option<&'a VersionCore> {
+    let RangeBound(_, first) = bounds.next()?;
+    first.pre_release.as_ref()?;
+
+    bounds
+        .all(|RangeBound(_, ver)| ver.pre_release.is_some() && ver.core == first.core)
+        .then_some(&first.core)
+}
+
+/// Returns the version core whose pre-release versions are matched by all of `units` with `MatchingAlg::Node`,
+/// if there are any.
+pub(crate) fn node_pre_release_core(units: &[RangeUnit]) -> Option<VersionCore> {
+    let core = pre_release_core(bounds(units))?;
+
+    (!to_interval(units)
+        .intersect(&Interval::pre_releases(core))
+        .is_empty())
+    .then(|| core.clone())
+}
+
+/// Finds the interval of versions matched by all of `units` with `MatchingAlg::Classic`.
+pub(crate) fn to_interval(units: &[RangeUnit]) -> Interval {
+    bounds(units)
+        .map(Interval::from_bound)
+        .reduce(|acc, interval| acc.intersect(&interval))
+        .unwrap_or_else(Interval::full)
+}
+
+/// Finds a pair of units (possibly the same one twice) that can't match any version together,
+/// as long as the whole group can't match any version.
+///
+/// Every unit matches a single interval of versions, so if there's no version matched by all of them,
+/// then there are two units that don't match any common version.
+pub(crate) fn find_conflict(units: &[RangeUnit]) -> Option<(usize, usize)> {
+    if !to_interval(units).is_empty() {
+        return None;
+    }
+
+    let intervals = units
+        .iter()
+        .map(|unit| to_interval(std::slice::from_ref(unit)))
+        .collect::<Vec<_>>();
+
+    (0..intervals.len()).find_map(|idx| {
+        (idx..intervals.len())
+            .find(|&other_idx| intervals[idx].intersect(&intervals[other_idx]).is_empty())
+            .map(|other_idx| (idx, other_idx))
+    })
+}
+
+#[test]
+fn test_find_conflict() {
+    let test = |s: &str| {
+        let units = s
+            .split(' ')
+            .map(|u| RangeUnit::parse(u).expect(u).0)
+            .collect::<Vec<_>>();
+
+        find_conflict(&units)
+    };
+
+    assert_eq!(None, test(">=1.0.0 <2.0.0"));
+    assert_eq!(Some((0, 1)), test(">2.0.0 <1.0.0"));
+    assert_eq!(Some((1, 2)), test(">=1.0.0 1.2.3 1.2.4"));
+    assert_eq!(Some((0, 0)), test("<0.0.0-0 >=1.0.0"));
+    assert_eq!(Some((1, 3)), test(">=1.0.0 <2.0.0 <3.0.0 >=2.0.0"));
+}
+
+/// Reduces a group of units (matched when all of them are) to at most one lower and one upper bound,
+/// or returns `None` if it can't match anything.
+///
+/// Both matching algorithms see the same versions before and after: if dropping the redundant bounds would
+/// let the group match pre-release versions with `MatchingAlg::Node`, then one of them is kept to prevent that.
+pub(crate) fn normalize(units: &[RangeUnit]) -> Option<Vec<RangeUnit>> {
+    let interval = to_interval(units);
+
+    if interval.is_empty() {
+        return None;
+    }
+
+    let intervals = bounds(units)
+        .map(|bound| (bound, Interval::from_bound(bound)))
+        .collect::<Vec<_>>();
+
+    let kept = if interval.end.is_some() && interval.end == successor(&interval.start) {
+        vec![RangeBound(RangeComparator::Equal, interval.start.clone())]
+    } else if let Some((bound, _)) = intervals.iter().find(|(_, i)| *i == interval) {
+        vec![(*bound).clone()]
+    } else {
+        let lowest = lowest_version();
+        let lower = intervals
+            .iter()
+            .find(|(_, i)| i.start == interval.start && i.end.is_none());
+        let upper = intervals
+            .iter()
+            .find(|(_, i)| i.end == interval.end && i.start == lowest);
+
+        lower
+            .filter(|_| interval.start != lowest)
+            .into_iter()
+            .chain(upper.filter(|_| interval.end.is_some()))
+            .map(|(bound, _)| (*bound).clone())
+            .collect()
+    };
+
+    let witness = match (
+        pre_release_core(bounds(units)),
+        pre_release_core(kept.iter()),
+    ) {
+        (None, Some(core)) => {
+            bounds(units).find(|RangeBound(_, ver)| ver.pre_release.is_none() || ver.core != *core)
+        }
+        _ => None,
+    };
+
+    let mut kept = kept.into_iter();
+    let mut out = match (kept.next(), kept.next()) {
+        (Some(lower), upper) => vec![RangeUnit::new(lower, upper)],
+        _ => vec![],
+    };
+
+    out.extend(witness.map(|bound| RangeUnit::new(bound.clone(), None)));
+
+    Some(out)
+}
+
+#[test]
+fn test_normalize() {
+    let test = |s: &str| {
+        let units = s
+            .split(' ')
+            .map(|u| RangeUnit::parse(u).expect(u).0)
+            .collect::<Vec<_>>();
+
+        normalize(&units).map(|units| {
+            units
+                .iter()
+                .map(|u| u.to_string())
+                .collect::<Vec<_>>()
+                .join(" ")
+        })
+    };
+
+    assert_eq!(Some(">=1.2.3".to_string()), test(">=1.2.3"));
+    assert_eq!(Some(">=1.2.3".to_string()), test(">=1.0.0 >=1.2.3"));
+    assert_eq!(Some("<1.0.0".to_string()), test("<2.0.0 <=1.5.0 <1.0.0"));
+    assert_eq!(
+        Some(">=1.2.3 <2.0.0-0".to_string()),
+        test(">=1.0.0 ^1.2.3 <3.0.0")
+    );
+    assert_eq!(Some(">1.2.3 <=1.5.0".to_string()), test(">1.2.3 <=1.5.0"));
+    assert_eq!(Some("1.2.3".to_string()), test(">=1.0.0 1.2.3 <=1.2.3"));
+    assert_eq!(Some("1.2.3".to_string()), test(">=1.2.3 <=1.2.3"));
+    assert_eq!(Some("1.2.3".to_string()), test(">=1.2.3 <1.2.4-0 <2.0.0"));
+    assert_eq!(
+        Some(">1.2.2 <1.2.4-0".to_string()),
+        test(">1.2.2 <1.2.4-0 <2.0.0")
+    );
+    assert_eq!(
+        Some("1.2.3-rc >=1.0.0".to_string()),
+        test(">=1.0.0 >=1.2.3-rc <1.2.3-rc.0")
+    );
+    assert_eq!(None, test(">=2.0.0 <1.0.0"));
+    assert_eq!(None, test(">1.2.3 <1.2.4-0"));
+    assert_eq!(None, test("1.2.3 1.2.4"));
+    // pre-release versions can't be matched with `MatchingAlg::Node` due to `>=1.0.0`, so it has to stay
+    assert_eq!(
+        Some(">=1.2.3-alpha <1.2.3-rc >=1.0.0".to_string()),
+        test(">=1.0.0 >=1.2.3-alpha <1.2.3-rc")
+    );
+    assert_eq!(
+        Some(">=1.2.3-alpha <1.2.3".to_string()),
+        test(">=1.0.0 >=1.2.3-alpha <1.2.3")
+    );
+    assert_eq!(
+        Some(">=1.2.3-beta <1.2.3-rc".to_string()),
+        test(">=1.2.3-alpha >=1.2.3-beta <1.2.3-rc")
+    );
+}
+
+/// Checks whether every version matched by all of `units` is also matched by all of `other`, with either `MatchingAlg`.
+pub(crate) fn is_covered_by(units: &[RangeUnit], other: &[RangeUnit]) -> bool {
+    to_interval(other).contains(&to_interval(units))
+        && node_pre_release_core(units)
+            .is_none_or(|core| node_pre_release_core(other) == Some(core))
+}
+
+/// Finds the bound that `units` start at, turning `=v` into `>=v`.
+pub(crate) fn lower_bound(units: &[RangeUnit]) -> Option<RangeBound> {
+    let start = to_interval(units).start;
+
+    bounds(units)
+        .find(|bound| {
+            let interval = Interval::from_bound(bound);
+            interval.start == start && (interval.end.is_none() || bound.0 == RangeComparator::Equal)
+        })
+        .map(|RangeBound(comp, ver)| match comp {
+            RangeComparator::Equal => RangeBound(RangeComparator::GreaterOrEqual, ver.clone()),
+            comp => RangeBound(*comp, ver.clone()),
+        })
+}
+
+/// Finds the bound that `units` end at, turning `=v` into `<=v`.
+pub(crate) fn upper_bound(units: &[RangeUnit]) -> Option<RangeBound> {
+    let end = to_interval(units).end;
+
+    bounds(units)
+        .find(|bound| {
+            let interval = Interval::from_bound(bound);
+            interval.end == end
+                && (interval.start == lowest_version() || bound.0 == RangeComparator::Equal)
+        })
+        .map(|RangeBound(comp, ver)| match comp {
+            RangeComparator::Equal => RangeBound(RangeComparator::LessOrEqual, ver.clone()),
+            comp => RangeBound(*comp, ver.clone()),
+        })
+}
+
+/// Combines two normalized groups into a single one that's matched by the same versions as either of them
+/// with both matching algorithms, or returns `None` if that's not possible (e.g. there's a gap between them).
+pub(crate) fn merge(units: &[RangeUnit], other: &[RangeUnit]) -> Option<Vec<RangeUnit>> {
+    if is_covered_by(other, units) {
+        return Some(units.to_vec());
+    }
+
+    if is_covered_by(units, other) {
+        return Some(other.to_vec());
+    }
+
+    let core = node_pre_release_core(units);
+
+    if core != node_pre_release_core(other) {
+        return None;
+    }
+
+    let interval = to_interval(units).hull(&to_interval(other))?;
+    let (first, last) = match to_interval(units).start == interval.start {
+        true => (units, other),
+        false => (other, units),
+    };
+    let last = match to_interval(first).end == interval.end {
+        true => first,
+        false => last,
+    };
+
+    let lower = match interval.start == lowest_version() {
+        true => None,
+        false => Some(lower_bound(first)?),
+    };
+    let upper = match interval.end {
+        Some(_) => Some(upper_bound(last)?),
+        None => None,
+    };
+
+    let mut out = match (lower, upper) {
+        (Some(lower), upper) => vec![RangeUnit::new(lower, upper)],
+        (None, Some(upper)) => vec![RangeUnit::new(upper, None)],
+        (None, None) => vec![RangeUnit::new(
+            RangeBound(RangeComparator::GreaterOrEqual, lowest_version()),
+            None,
+        )],
+    };
+
+    match (core, node_pre_release_core(&out)) {
+        (core, merged_core) if core == merged_core => {}
+        // one of the redundant bounds has to be kept to prevent matching pre-release versions
+        (None, Some(merged_core)) => {
+            let witness = bounds(units).chain(bounds(other)).find(|bound| {
+                Interval::from_bound(bound).contains(&interval)
+                    && (bound.1.pre_release.is_none() || bound.1.core != merged_core)
+            })?;
+
+            out.push(RangeUnit::new(witness.clone(), None));
+        }
+        _ => return None,
+    }
+
+    Some(out)
+}
+
+#[test]
+fn test_merge() {
+    let group = |s: &str| {
+        normalize(
+            &s.split(' ')
+                .map(|u| RangeUnit::parse(u).expect(u).0)
+                .collect::<Vec<_>>(),
+        )
+        .unwrap()
+    };
+    let test = |a, b| {
+        merge(&group(a), &group(b)).map(|units| {
+            units
+                .iter()
+                .map(|u| u.to_string())
+                .collect::<Vec<_>>()
+                .join(" ")
+        })
+    };
+
+    assert_eq!(
+        Some(">=1.2.0 <2.0.0-0".to_string()),
+        test("^1.2.0", "^1.5.0")
+    );
+    assert_eq!(
+        Some(">=1.2.0 <2.0.0-0".to_string()),
+        test("^1.5.0", "^1.2.0")
+    );
+    assert_eq!(
+        Some(">=1.0.0 <3.0.0".to_string()),
+        test(">=1.0.0 <2.0.0", ">=2.0.0 <3.0.0")
+    );
+    assert_eq!(
+        Some(">=1.0.0 <=2.0.0".to_string()),
+        test(">=1.0.0 <2.0.0", "2.0.0")
+    );
+    assert_eq!(
+        Some(">=2.0.0 <3.0.0".to_string()),
+        test("2.0.0", ">2.0.0 <3.0.0")
+    );
+    assert_eq!(
+        Some(">1.0.0 <3.0.0".to_string()),
+        test(">1.0.0 <2.0.0", ">=1.5.0 <3.0.0")
+    );
+    assert_eq!(Some(">=0.0.0".to_string()), test("^1.2.3", "*"));
+    assert_eq!(Some("<2.0.0".to_string()), test("<1.0.0", ">=0.5.0 <2.0.0"));
+    assert_eq!(None, test(">=1.0.0 <2.0.0", ">2.0.0 <3.0.0"));
+    assert_eq!(None, test("1.2.3", "1.2.4"));
+    // `<1.0.0` doesn't match any pre-release versions with `MatchingAlg::Node`, but `>=0.0.0-0` would
+    assert_eq!(None, test("<1.0.0", ">=1.0.0"));
+    // only the second group matches `1.2.3-beta` with `MatchingAlg::Node`
+    assert_eq!(
+        None,
+        test(">=1.0.0 <1.2.3-alpha", ">=1.2.3-alpha <1.2.3-rc")
+    );
+    assert_eq!(
+        Some(">=1.2.3-alpha <1.2.3-rc >=1.0.0".to_string()),
+        test(
+            ">=1.2.3-alpha <1.2.3-beta >=1.0.0",
+            ">=1.2.3-beta <1.2.3-rc >=1.0.0"
+        )
+    );
+}
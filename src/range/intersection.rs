@@ -0,0 +1,134 @@
+use super::group;
+use super::matcher::MatchingAlg;
+use super::set::VersionSet;
+use super::unit::RangeUnit;
+use super::Range;
+
+impl Range {
+    /// Finds the range of versions that are matched by both `self` and `other`, with either `MatchingAlg`.
+    ///
+    /// Every unit group of `self` is combined with every unit group of `other`. The resulting groups are then reduced
+    /// to their tightest bounds, and the ones that can't match anything (e.g. `>=2.0.0 <1.0.0`) are dropped.
+    /// If nothing is left, then the result is `<0.0.0-0`, which doesn't match any version.
+    ///
+    /// The result is normalized, see `normalize`.
+    pub fn intersect(&self, other: &Self) -> Self {
+        let mut unit_groups = Vec::<Vec<RangeUnit>>::new();
+
+        for units in self.unit_groups() {
+            for other_units in other.unit_groups() {
+                let combined = units.iter().chain(other_units).cloned().collect::<Vec<_>>();
+
+                if let Some(units) = group::normalize(&combined) {
+                    if !unit_groups.contains(&units) {
+                        unit_groups.push(units);
+                    }
+                }
+            }
+        }
+
+        Self::from_groups(unit_groups).normalize()
+    }
+
+    /// Checks whether any version is matched by both `self` and `other` with the given `alg`,
+    /// following `node-semver`'s `intersects`.
+    ///
+    /// Unlike `intersect`, it doesn't build a new range, so it's suitable for quick conflict detection.
+    pub fn intersects(&self, other: &Self, alg: MatchingAlg) -> bool {
+        VersionSet::new(self, alg).intersects(&VersionSet::new(other, alg))
+    }
+}
+
+#[test]
+fn test_intersect() {
+    let test = |a, b| {
+        Range::from(a)
+            .unwrap()
+            .intersect(&Range::from(b).unwrap())
+            .to_string()
+    };
+
+    assert_eq!(">=1.2.3 <2.0.0-0", test("^1.2.3", "*"));
+    assert_eq!(">=1.4.0 <1.5.0-0", test("^1.2.3", "~1.4"));
+    assert_eq!(">=1.2.3 <1.5.0", test(">=1.0.0 <1.5.0", ">=1.2.3 <2.0.0"));
+    assert_eq!("1.2.3", test("^1.2.3", "1.2.3"));
+    assert_eq!("<0.0.0-0", test("^1.2.3", "^2.0.0"));
+    assert_eq!("<0.0.0-0", test(">=2.0.0", "<1.0.0"));
+    assert_eq!(
+        ">=1.2.0 <1.3.0-0 || >=2.0.0 <2.1.0-0",
+        test("1.x || 2.x", "~1.2 || ~2.0")
+    );
+    assert_eq!(
+        ">=1.5.0 <2.0.0-0 || >=3.0.0 <3.1.0",
+        test("^1.2.3 || ^3.0.0", ">=1.5.0 <2.0.0 || >=2.5.0 <3.1.0")
+    );
+    assert_eq!(
+        ">=1.2.3-alpha <1.2.3-rc >=1.0.0",
+        test(">=1.0.0", ">=1.2.3-alpha <1.2.3-rc")
+    );
+}
+
+#[test]
+fn test_intersect_matches() {
+    super::testing::assert_for_range_pairs(
+        |a, b, _| a.intersect(b),
+        |both, alg, ver, in_a, in_b| both.is_matched_by(alg, ver) == (in_a && in_b),
+    );
+}
+
+#[test]
+fn test_intersects() {
+    use MatchingAlg::*;
+
+    let test = |a, b, alg| {
+        Range::from(a)
+            .unwrap()
+            .intersects(&Range::from(b).unwrap(), alg)
+    };
+
+    for alg in [Classic, Node] {
+        assert!(test("^1.2.3", "~1.4", alg));
+        assert!(test("^1.2.3", "1.9.9", alg));
+        assert!(!test("^1.2.3", "^2.0.0", alg));
+        assert!(!test("^1.2.3", "2.0.0-0", alg));
+        assert!(!test("<2.0.0-0", ">=2.0.0-0", alg));
+        assert!(test("<=2.0.0-0", ">=2.0.0-0", Classic));
+        assert!(!test(">1.2.3", "<1.2.4-0", alg));
+        assert!(test("1.2.3", "<=1.2.3", alg));
+        assert!(!test("1.2.3", "<1.2.3", alg));
+        assert!(!test("1.2.3", ">1.2.3", alg));
+        assert!(test("1.2.3 || 1.2.5", ">1.2.4", alg));
+        assert!(!test("1.2.3 || 1.2.5", ">1.2.3 <1.2.5", alg));
+        assert!(!test("<0.0.0-0", "*", alg));
+    }
+
+    // only pre-release versions are matched by both
+    assert!(test(">=1.2.3-alpha <1.2.3", ">=1.0.0 <1.2.3-beta", Classic));
+    assert!(!test(">=1.2.3-alpha <1.2.3", ">=1.0.0 <1.2.3-beta", Node));
+    assert!(test(
+        ">=1.2.3-alpha <1.2.3-rc",
+        ">1.2.3-alpha.1 <=1.2.3-beta",
+        Node
+    ));
+    assert!(!test(
+        ">=1.2.3-alpha <1.2.3-rc",
+        ">=1.2.4-alpha <1.2.4",
+        Node
+    ));
+    assert!(test("<=2.0.0-0", ">=2.0.0-0", Node));
+}
+
+#[test]
+fn test_intersects_matches() {
+    super::testing::assert_for_range_pairs(
+        |a, b, alg| {
+            let intersects = a.intersects(b, alg);
+            let both = a.intersect(b);
+
+            // the intersection is exact, so it's non-empty if and only if the ranges intersect
+            assert_eq!(both.intersects(&both, alg), intersects, "{} ∩ {}", a, b);
+            intersects
+        },
+        |&intersects, _, _, in_a, in_b| intersects || !(in_a && in_b),
+    );
+}
@@ -0,0 +1,269 @@
+use std::cmp;
+
+use super::bound::RangeBound;
+use super::comparator::RangeComparator;
+use crate::version::core::VersionCore;
+use crate::version::difference::VersionDiff;
+use crate::version::pre_release::{PreReleaseIdentifier, VersionPreRelease};
+use crate::version::Version;
+
+/// A half-open interval of versions `[start, end)` as seen by `MatchingAlg::Classic`, where `end` being `None` means
+/// that there's no upper limit.
+///
+/// Since versions are discrete, every bound can be expressed this way: e.g. `>1.2.3` is `[1.2.4-0, ∞)`,
+/// because no version lies strictly between `1.2.3` and `1.2.4-0`.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub(crate) struct Interval {
+    pub(crate) start: Version,
+    pub(crate) end: Option<Version>,
+}
+
+/// The smallest possible version, `0.0.0-0`.
+pub(crate) fn lowest_version() -> Version {
+    Version::new(0, 0, 0).with_pre_release(VersionPreRelease::default())
+}
+
+/// Finds the smallest version that's greater than `ver`, if there's any.
+pub(crate) fn successor(ver: &Version) -> Option<Version> {
+    match &ver.pre_release {
+        Some(pre) => {
+            let mut ids = pre.0.clone();
+            ids.push(PreReleaseIdentifier::Numeric(0));
+
+            Some(Version {
+                core: ver.core.clone(),
+                pre_release: Some(VersionPreRelease(ids)),
+                build: None,
+            })
+        }
+        None => ver.to_upper_bound(VersionDiff::PrePatch),
+    }
+}
+
+#[test]
+fn test_successor() {
+    let test = |s: &str| successor(&Version::from(s).unwrap()).map(|v| v.to_string());
+    let max = u64::MAX;
+
+    assert_eq!(Some("1.2.4-0".to_string()), test("1.2.3"));
+    assert_eq!(Some("1.2.3-0.0".to_string()), test("1.2.3-0"));
+    assert_eq!(Some("1.2.3-rc.1.0".to_string()), test("1.2.3-rc.1"));
+    assert_eq!(Some("2.0.0-0".to_string()), test(&format!("1.{max}.{max}")));
+    assert_eq!(None, test(&format!("{max}.{max}.{max}")));
+}
+
+/// Compares exclusive upper limits, `None` being the largest one.
+fn cmp_ends(a: &Option<Version>, b: &Option<Version>) -> cmp::Ordering {
+    match (a, b) {
+        (Some(a), Some(b)) => a.cmp(b),
+        (a, b) => b.is_some().cmp(&a.is_some()),
+    }
+}
+
+impl Interval {
+    pub(crate) fn full() -> Self {
+        Self {
+            start: lowest_version(),
+            end: None,
+        }
+    }
+
+    /// Covers all pre-release versions of `core`, i.e. `[core-0, core)`.
+    pub(crate) fn pre_releases(core: &VersionCore) -> Self {
+        Self {
+            start: Version {
+                core: core.clone(),
+                pre_release: Some(VersionPreRelease::default()),
+                build: None,
+            },
+            end: Some(Version {
+                core: core.clone(),
+                pre_release: None,
+                build: None,
+            }),
+        }
+    }
+
+    pub(crate) fn from_bound(bound: &RangeBound) -> Self {
+        use RangeComparator::*;
+
+        let RangeBound(comp, ver) = bound;
+
+        match comp {
+            Less => Self {
+                start: lowest_version(),
+                end: Some(ver.clone()),
+            },
+            LessOrEqual => Self {
+                start: lowest_version(),
+                end: successor(ver),
+            },
+            Equal => Self {
+                start: ver.clone(),
+                end: successor(ver),
+            },
+            GreaterOrEqual => Self {
+                start: ver.clone(),
+                end: None,
+            },
+            Greater => match successor(ver) {
+                Some(start) => Self { start, end: None },
+                None => Self {
+                    start: ver.clone(),
+                    end: Some(ver.clone()),
+                },
+            },
+        }
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.end.as_ref().is_some_and(|end| *end <= self.start)
+    }
+
+    /// Checks whether every version in `other` is also in `self`.
+    pub(crate) fn contains(&self, other: &Self) -> bool {
+        other.is_empty() || (self.start <= other.start && cmp_ends(&other.end, &self.end).is_le())
+    }
+
+    /// Finds the smallest interval that covers both `self` and `other`, as long as they overlap or touch,
+    /// so that no versions are added in between.
+    pub(crate) fn hull(&self, other: &Self) -> Option<Self> {
+        let (first, second) = match self.start <= other.start {
+            true => (self, other),
+            false => (other, self),
+        };
+
+        if first.end.as_ref().is_some_and(|end| *end < second.start) {
+            return None;
+        }
+
+        Some(Self {
+            start: first.start.clone(),
+            end: cmp::max_by(first.end.clone(), second.end.clone(), cmp_ends),
+        })
+    }
+
+    pub(crate) fn intersect(&self, other: &Self) -> Self {
+        let end = match (&self.end, &other.end) {
+            (Some(a), Some(b)) => Some(a.min(b).clone()),
+            (a, b) => a.as_ref().or(b.as_ref()).cloned(),
+        };
+
+        Self {
+            start: self.start.clone().max(other.start.clone()),
+            end,
+        }
+    }
+}
+
+impl PartialOrd for Interval {
+    fn partial_cmp(&self, other: &Self) -> Option<cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// Orders intervals by their start first, and by their end then.
+impl Ord for Interval {
+    fn cmp(&self, other: &Self) -> cmp::Ordering {
+        self.start
+            .cmp(&other.start)
+            .then_with(|| cmp_ends(&self.end, &other.end))
+    }
+}
+
+#[test]
+fn test_from_bound() {
+    use RangeComparator::*;
+
+    let test = |comp, s| {
+        let interval = Interval::from_bound(&RangeBound(comp, Version::from(s).unwrap()));
+
+        (
+            interval.start.to_string(),
+            interval.end.map(|v| v.to_string()),
+        )
+    };
+
+    assert_eq!(
+        ("0.0.0-0".to_string(), Some("1.2.3".to_string())),
+        test(Less, "1.2.3")
+    );
+    assert_eq!(
+        ("0.0.0-0".to_string(), Some("1.2.4-0".to_string())),
+        test(LessOrEqual, "1.2.3")
+    );
+    assert_eq!(
+        ("1.2.3-rc".to_string(), Some("1.2.3-rc.0".to_string())),
+        test(Equal, "1.2.3-rc")
+    );
+    assert_eq!(("1.2.3".to_string(), None), test(GreaterOrEqual, "1.2.3"));
+    assert_eq!(("1.2.4-0".to_string(), None), test(Greater, "1.2.3"));
+}
+
+#[test]
+fn test_is_empty() {
+    let test = |a: (RangeComparator, &str), b: (RangeComparator, &str)| {
+        Interval::from_bound(&RangeBound(a.0, Version::from(a.1).unwrap()))
+            .intersect(&Interval::from_bound(&RangeBound(
+                b.0,
+                Version::from(b.1).unwrap(),
+            )))
+            .is_empty()
+    };
+
+    use RangeComparator::*;
+
+    assert!(!test((GreaterOrEqual, "1.0.0"), (Less, "2.0.0")));
+    assert!(test((GreaterOrEqual, "2.0.0"), (Less, "1.0.0")));
+    assert!(test((GreaterOrEqual, "1.0.0"), (Less, "1.0.0")));
+    assert!(!test((GreaterOrEqual, "1.0.0"), (LessOrEqual, "1.0.0")));
+    assert!(test((Greater, "1.2.3"), (Less, "1.2.4-0")));
+    assert!(!test((Greater, "1.2.3"), (LessOrEqual, "1.2.4-0")));
+    assert!(!test((Greater, "1.2.3-rc"), (Less, "1.2.3")));
+    assert!(test(
+        (Greater, &format!("{0}.{0}.{0}", u64::MAX)),
+        (GreaterOrEqual, "0.0.0")
+    ));
+}
+
+#[test]
+fn test_hull() {
+    use RangeComparator::*;
+
+    let interval = |lower: &str, upper: &str| {
+        Interval::from_bound(&RangeBound(GreaterOrEqual, Version::from(lower).unwrap())).intersect(
+            &Interval::from_bound(&RangeBound(Less, Version::from(upper).unwrap())),
+        )
+    };
+    let test = |a: &Interval, b: &Interval| {
+        a.hull(b)
+            .map(|i| (i.start.to_string(), i.end.map(|v| v.to_string())))
+    };
+
+    let (a, b) = (interval("1.0.0", "2.0.0"), interval("1.5.0", "3.0.0"));
+    assert_eq!(
+        Some(("1.0.0".to_string(), Some("3.0.0".to_string()))),
+        test(&a, &b)
+    );
+    assert_eq!(test(&a, &b), test(&b, &a));
+
+    let (a, b) = (interval("1.0.0", "2.0.0"), interval("2.0.0", "3.0.0"));
+    assert_eq!(
+        Some(("1.0.0".to_string(), Some("3.0.0".to_string()))),
+        test(&a, &b)
+    );
+    assert_eq!(
+        None,
+        test(&interval("1.0.0", "2.0.0"), &interval("2.0.1", "3.0.0"))
+    );
+    assert_eq!(
+        Some(("1.0.0".to_string(), None)),
+        test(
+            &interval("1.0.0", "2.0.0"),
+            &Interval::from_bound(&RangeBound(GreaterOrEqual, Version::new(1, 5, 0)))
+        )
+    );
+    assert!(a.contains(&interval("1.2.0", "1.3.0")));
+    assert!(!a.contains(&interval("1.2.0", "2.0.1")));
+    assert!(Interval::full().contains(&a));
+}
@@ -6,7 +6,7 @@ use super::unit::RangeUnit;
 use super::Range;
 use crate::version::Version;
 
-#[derive(Clone, Copy, PartialEq)]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
 pub enum MatchingAlg {
     Classic,
     Node,
@@ -38,7 +38,7 @@ impl RangeUnit {
             && self
                 .extra_bound
                 .as_ref()
-                .map_or(true, |b| b.is_matched_by(alg, ver))
+                .is_none_or(|b| b.is_matched_by(alg, ver))
     }
 }
 
@@ -125,4 +125,7 @@ fn test_range_is_matched_by() {
     assert!(test("2.0.0", ">=2.0.0 || >=1.0.0 <1.5.0"));
     assert!(test("1.2.3", ">=2.0.0 || >=1.0.0 <1.5.0"));
     assert!(!test("1.5.0", ">=2.0.0 || >=1.0.0 <1.5.0"));
+    assert!(test("20241017123000.0.0", ">20241017122959"));
+    assert!(!test("20241017123000.0.0", "^4294967296.0.0"));
+    assert!(test("4294967296.1.0", "4294967296.x"));
 }
@@ -1,12 +1,41 @@
-use std::fmt;
+use std::{fmt, ops, slice, str};
 
+use bound::RangeBound;
+use comparator::RangeComparator;
+use error::{RangeParseError, RangeParseErrorKind};
+use interval::lowest_version;
+use matcher::MatchingAlg;
+use set::VersionSet;
 use unit::RangeUnit;
 
+use crate::version::Version;
+
 pub(crate) mod bound;
+pub(crate) mod canonical;
 pub(crate) mod comparator;
+pub(crate) mod complement;
+pub(crate) mod conflict;
+pub(crate) mod construct;
+pub(crate) mod error;
+pub(crate) mod extremum;
+pub(crate) mod group;
+pub(crate) mod intersection;
+pub(crate) mod interval;
 pub(crate) mod matcher;
+pub(crate) mod position;
+pub(crate) mod selection;
+pub(crate) mod set;
+pub(crate) mod simplify;
+pub(crate) mod subset;
+pub(crate) mod sugar;
+#[cfg(test)]
+mod testing;
+pub(crate) mod union;
 pub(crate) mod unit;
 
+/// Byte spans of the units of a single unit group within the parsed string.
+type UnitSpans = Vec<ops::Range<usize>>;
+
 #[derive(Clone, Debug, PartialEq)]
 pub enum Range {
     Just(RangeUnit), // allocating for one unit only most of the time would be a waste
@@ -63,12 +92,54 @@ impl Range {
     ///
     /// Note that it is significantly stricter than the reference algorithm used by that library,
     /// and will reject many ambiguous inputs.
-    pub fn from(s: &str) -> Option<Self> {
-        let mut r = s;
+    pub fn from(s: &str) -> Result<Self, RangeParseError> {
+        Self::parse(s).map(|(range, _)| range)
+    }
+
+    /// Same as `from`, but also rejects the range if any of its unit groups can't match a version
+    /// (e.g. `>2.0.0 <1.0.0`), in which case the error spans the conflicting units.
+    pub fn from_satisfiable(s: &str) -> Result<Self, RangeParseError> {
+        let (range, spans) = Self::parse(s)?;
+
+        for (units, spans) in range.unit_groups().into_iter().zip(spans) {
+            if let Some((a, b)) = group::find_conflict(units) {
+                let (start, end) = (spans[a].start, spans[b].end);
+
+                return Err(RangeParseError::new(
+                    s,
+                    &s[start..],
+                    end - start,
+                    RangeParseErrorKind::Unsatisfiable,
+                ));
+            }
+        }
+
+        Ok(range)
+    }
+
+    /// Parses the range, also returning the byte spans of its units, grouped the same way as `unit_groups`.
+    fn parse(s: &str) -> Result<(Self, Vec<UnitSpans>), RangeParseError> {
+        use RangeParseErrorKind::*;
+
+        let mut r = s.trim_start_matches(' ');
         let mut out = None::<Self>;
+        let mut spans = vec![vec![]];
+
+        if r.is_empty() {
+            return Err(RangeParseError::new(s, s, s.len(), Empty));
+        }
 
         while !r.is_empty() {
-            let (unit, t) = RangeUnit::parse(r.trim_start_matches(' '))?;
+            if r.starts_with("||") {
+                return Err(RangeParseError::new(s, r, 2, DanglingOr));
+            }
+
+            let (unit, t) =
+                RangeUnit::parse(r).map_err(|err| RangeParseError::from_unit(s, r, err))?;
+
+            if let Some(unit_spans) = spans.last_mut() {
+                unit_spans.push((s.len() - r.len())..(s.len() - t.len()));
+            }
 
             out = match out {
                 None => Some(Self::Just(unit)),
@@ -77,21 +148,26 @@ impl Range {
                     units.push(unit);
                     Some(Self::All(units))
                 }
-                Some(Self::Any(mut unit_groups)) => match unit_groups.last_mut() {
-                    Some(units) => {
+                Some(Self::Any(mut unit_groups)) => {
+                    if let Some(units) = unit_groups.last_mut() {
                         units.push(unit);
-                        Some(Self::Any(unit_groups))
                     }
-                    None => return None,
-                },
+
+                    Some(Self::Any(unit_groups))
+                }
             };
 
             r = t.trim_start_matches(' ');
 
             if let Some(t) = r.strip_prefix("||") {
-                r = t;
+                if t.trim_start_matches(' ').is_empty() {
+                    return Err(RangeParseError::new(s, r, 2, DanglingOr));
+                }
+
+                r = t.trim_start_matches(' ');
+                spans.push(vec![]);
                 out = match out {
-                    None => return None,
+                    None => None,
                     Some(Self::Just(unit)) => Some(Self::Any(vec![vec![unit], vec![]])),
                     Some(Self::All(units)) => Some(Self::Any(vec![units, vec![]])),
                     Some(Self::Any(mut unit_groups)) => {
@@ -102,28 +178,157 @@ impl Range {
             }
         }
 
-        match out {
-            Some(Self::Any(unit_groups)) => match unit_groups.last() {
-                Some(units) if units.is_empty() => None,
-                _ => Some(Self::Any(unit_groups)),
-            },
-            rest => rest,
+        out.map(|range| (range, spans))
+            .ok_or_else(|| RangeParseError::new(s, s, s.len(), Empty))
+    }
+}
+
+impl Range {
+    /// Lists the groups of units (also known as comparator sets) that the range consists of,
+    /// i.e. the range is matched if all units of any group are.
+    pub fn unit_groups(&self) -> Vec<&[RangeUnit]> {
+        match self {
+            Self::Just(unit) => vec![slice::from_ref(unit)],
+            Self::All(units) => vec![units],
+            Self::Any(unit_groups) => unit_groups.iter().map(Vec::as_slice).collect(),
+        }
+    }
+
+    /// Returns the version that the range is pinned to if it consists of a single `=` unit (e.g. `1.2.3` or `=1.2.3`).
+    pub fn as_exact(&self) -> Option<&Version> {
+        match self {
+            Self::Just(RangeUnit {
+                bound: RangeBound(RangeComparator::Equal, ver),
+                extra_bound: None,
+                ..
+            }) => Some(ver),
+            _ => None,
+        }
+    }
+
+    /// Builds a range out of the given (non-empty) groups of units, see `unit_groups`.
+    ///
+    /// If there are no groups at all, then the result is `<0.0.0-0`, which doesn't match any version.
+    pub(crate) fn from_groups(mut unit_groups: Vec<Vec<RangeUnit>>) -> Self {
+        match unit_groups.len() {
+            0 => Self::Just(RangeUnit::new(
+                RangeBound(RangeComparator::Less, lowest_version()),
+                None,
+            )),
+            1 => {
+                let mut units = unit_groups.remove(0);
+
+                match units.len() {
+                    1 => Self::Just(units.remove(0)),
+                    _ => Self::All(units),
+                }
+            }
+            _ => Self::Any(unit_groups),
         }
     }
 }
 
+impl Range {
+    /// Compiles the range into the sorted intervals of versions that it matches with the given `alg`,
+    /// which is faster to match many versions against (see `VersionSet`).
+    pub fn to_version_set(&self, alg: MatchingAlg) -> VersionSet {
+        VersionSet::new(self, alg)
+    }
+}
+
+impl str::FromStr for Range {
+    type Err = RangeParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::from(s)
+    }
+}
+
+impl TryFrom<&str> for Range {
+    type Error = RangeParseError;
+
+    fn try_from(s: &str) -> Result<Self, Self::Error> {
+        Self::from(s)
+    }
+}
+
 #[test]
 fn test_from() {
+    use RangeParseErrorKind::*;
+
     let parse = |s| Range::from(s).expect(s).to_string();
+    let err = |s| {
+        Range::from(s)
+            .map_err(|e| (e.kind(), e.span()))
+            .unwrap_err()
+    };
 
-    assert_eq!(None, Range::from("  "));
+    assert_eq!((Empty, 0..2), err("  "));
     assert_eq!("1.2.3", parse("1.2.3"));
-    assert_eq!(None, Range::from("1.2.3 ???"));
+    assert_eq!((InvalidUnit, 6..9), err("1.2.3 ???"));
     assert_eq!("1.2.3", parse("   1.2.3    "));
     assert_eq!("1.2.3 <2.0.0", parse("1.2.3 <2.0.0"));
     assert_eq!("1.2.3 4.5.6 7.8.9", parse("1.2.3 4.5.6 7.8.9"));
     assert_eq!("1.2.3 || 4.5.6", parse("1.2.3 || 4.5.6"));
-    assert_eq!(None, Range::from("1.2.3 ||"));
+    assert_eq!((DanglingOr, 6..8), err("1.2.3 ||"));
+    assert_eq!((DanglingOr, 6..8), err("1.2.3 ||   "));
+    assert_eq!((DanglingOr, 0..2), err("|| 1.2.3"));
+    assert_eq!((DanglingOr, 9..11), err("1.2.3 || || 4.5.6"));
     assert_eq!("1.2.3 4.5.6 || 7.8.9", parse("1.2.3 4.5.6 || 7.8.9"));
     assert_eq!("1.2.3 || 4.5.6 || 7.8.9", parse("1.2.3 || 4.5.6 || 7.8.9"));
+    assert_eq!("1.2.3 || >=0.0.0", parse("1.2.3 || ^*"));
+    assert_eq!((PreReleaseOnPattern, 9..18), err("1.2.3 || ^1.2-beta <2"));
+    assert_eq!((ComparatorBeforeHyphen, 0..6), err(">1 - 2"));
+    assert_eq!((PreReleaseOnPattern, 7..18), err("<1.0.0 ~1.x-beta.1||2"));
+    assert_eq!("1.2.3", "1.2.3".parse::<Range>().unwrap().to_string());
+}
+
+#[test]
+fn test_from_version_error() {
+    use crate::version::error::VersionParseErrorKind::*;
+
+    let err = Range::from(">=1.0.0 <1.2.03 || 2").unwrap_err();
+    let ver_err = err.version_error().unwrap();
+
+    assert_eq!(RangeParseErrorKind::InvalidUnit, err.kind());
+    assert_eq!(8..15, err.span());
+    assert_eq!(LeadingZero, ver_err.kind());
+    assert_eq!("<1.2.03", ver_err.input());
+    assert_eq!(5, ver_err.offset());
+    assert!(std::error::Error::source(&err).is_some());
+}
+
+#[test]
+fn test_from_satisfiable() {
+    use RangeParseErrorKind::*;
+
+    let parse = |s| Range::from_satisfiable(s).expect(s).to_string();
+    let err = |s| {
+        Range::from_satisfiable(s)
+            .map_err(|e| (e.kind(), e.span()))
+            .unwrap_err()
+    };
+
+    assert_eq!(">=1.2.3 <2.0.0-0", parse("^1.2.3"));
+    assert_eq!("1.2.3 || 4.5.6", parse("1.2.3 || 4.5.6"));
+    assert_eq!((Unsatisfiable, 0..13), err(">2.0.0 <1.0.0"));
+    assert_eq!((Unsatisfiable, 0..11), err("1.2.3 4.5.6 7.8.9"));
+    assert_eq!((Unsatisfiable, 10..18), err("^1.2.3 || <0.0.0-0"));
+    assert_eq!(
+        (Unsatisfiable, 10..31),
+        err("^1.2.3 || >=1.2.0 1.0.0 - 1.1.0 <2")
+    );
+    assert_eq!((InvalidUnit, 6..9), err("1.2.3 ???"));
+}
+
+#[test]
+fn test_as_exact() {
+    let test = |s| Range::from(s).unwrap().as_exact().map(|v| v.to_string());
+
+    assert_eq!(Some("1.2.3".to_string()), test("1.2.3"));
+    assert_eq!(Some("1.2.3-rc".to_string()), test("=1.2.3-rc+build"));
+    assert_eq!(None, test(">=1.2.3"));
+    assert_eq!(None, test("1.2"));
+    assert_eq!(None, test("1.2.3 || 1.2.3"));
+    assert_eq!(None, test(">=1.2.3 <=1.2.3"));
 }
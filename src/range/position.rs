@@ -0,0 +1,86 @@
+use super::bound::RangeBound;
+use super::comparator::RangeComparator;
+use super::interval::Interval;
+use super::matcher::MatchingAlg;
+use super::set::VersionSet;
+use super::Range;
+use crate::version::Version;
+
+/// Where a version lies relative to the versions matched by a range, see `Range::position_of`.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum RangePosition {
+    /// The version is less than every matched version (`node-semver`'s `ltr`).
+    Below,
+    /// The version is matched.
+    Within,
+    /// The version is greater than every matched version (`node-semver`'s `gtr`).
+    Above,
+    /// The version isn't matched, but lies in a gap between matched versions (e.g. `1.5.0` for `^1.0.0 <1.5.0 || ^2.0.0`).
+    Between,
+}
+
+impl Range {
+    /// Finds where `ver` lies relative to the versions matched by the range with the given `alg`,
+    /// mirroring `node-semver`'s `outside`, `gtr` and `ltr`.
+    ///
+    /// Versions are compared as usual, regardless of `alg`: e.g. `1.3.0-rc` is `Above` `~1.2.0` with
+    /// `MatchingAlg::Node`, even though `>1.2.0` doesn't match it in that case.
+    /// If the range doesn't match anything, then every version is `Below` it.
+    pub fn position_of(&self, ver: &Version, alg: MatchingAlg) -> RangePosition {
+        if self.is_matched_by(alg, ver) {
+            return RangePosition::Within;
+        }
+
+        let set = VersionSet::new(self, alg);
+        let is_matched_in =
+            |comp| set.intersects_interval(&Interval::from_bound(&RangeBound(comp, ver.clone())));
+
+        if !is_matched_in(RangeComparator::Less) {
+            RangePosition::Below
+        } else if !is_matched_in(RangeComparator::Greater) {
+            RangePosition::Above
+        } else {
+            RangePosition::Between
+        }
+    }
+}
+
+#[test]
+fn test_position_of() {
+    use MatchingAlg::*;
+    use RangePosition::*;
+
+    let test = |v, s, alg| {
+        Range::from(s)
+            .unwrap()
+            .position_of(&Version::from(v).unwrap(), alg)
+    };
+
+    for alg in [Classic, Node] {
+        assert_eq!(Below, test("0.9.0", "^1.0.0", alg));
+        assert_eq!(Within, test("1.2.3", "^1.0.0", alg));
+        assert_eq!(Above, test("2.0.0", "^1.0.0", alg));
+        assert_eq!(Above, test("2.0.0-rc", "^1.0.0", alg));
+        assert_eq!(Below, test("1.0.0-rc", "^1.0.0", alg));
+        assert_eq!(Between, test("1.5.0", "^1.0.0 <1.5.0 || ^2.0.0", alg));
+        assert_eq!(Between, test("1.2.4", "1.2.3 || 1.2.5", alg));
+        assert_eq!(Above, test("1.2.6", "1.2.3 || 1.2.5", alg));
+        assert_eq!(Below, test("1.2.2", "1.2.3 || 1.2.5", alg));
+        assert_eq!(Above, test("1.2.3", "<1.2.3", alg));
+        assert_eq!(Below, test("1.2.3", ">1.2.3", alg));
+        assert_eq!(Below, test("1.2.3", ">2.0.0 <1.0.0", alg));
+    }
+
+    // with `Node`, no pre-release version of `1.3.0` is matched
+    assert_eq!(Within, test("1.3.0-rc", "^1.0.0", Classic));
+    assert_eq!(Above, test("1.3.0-rc", "~1.2.0", Node));
+    assert_eq!(Between, test("1.3.0-rc", "~1.2.0 || ^1.4.0", Node));
+    assert_eq!(
+        Between,
+        test(
+            "1.2.3-beta",
+            ">=1.2.3-alpha <1.2.3-beta || >=1.2.3-rc <1.2.3-rc.5",
+            Node
+        )
+    );
+}
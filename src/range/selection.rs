@@ -0,0 +1,137 @@
+use super::matcher::MatchingAlg;
+use super::Range;
+use crate::version::Version;
+
+/// A policy for choosing one of the candidate versions matched by a range, see `Range::best_match`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Preference<'a> {
+    /// The largest matching version.
+    Highest,
+    /// The smallest matching version.
+    Lowest,
+    /// The largest matching version without a pre-release tag, or the largest matching pre-release version if
+    /// there are no others.
+    Stable,
+    /// The candidate equal to the given (e.g. previously locked) version if it's still matched,
+    /// or the largest matching version otherwise.
+    Locked(&'a Version),
+}
+
+impl Range {
+    /// Finds the largest of `versions` that's matched by the range with the given `alg`,
+    /// following `node-semver`'s `maxSatisfying`.
+    pub fn max_satisfying<'a>(
+        &self,
+        versions: impl IntoIterator<Item = &'a Version>,
+        alg: MatchingAlg,
+    ) -> Option<&'a Version> {
+        self.best_match(versions, alg, Preference::Highest)
+    }
+
+    /// Finds the smallest of `versions` that's matched by the range with the given `alg`,
+    /// following `node-semver`'s `minSatisfying`.
+    pub fn min_satisfying<'a>(
+        &self,
+        versions: impl IntoIterator<Item = &'a Version>,
+        alg: MatchingAlg,
+    ) -> Option<&'a Version> {
+        self.best_match(versions, alg, Preference::Lowest)
+    }
+
+    /// Chooses one of `versions` that's matched by the range with the given `alg` according to `pref`.
+    ///
+    /// The candidates are only borrowed, and are expected to be unique (otherwise it's unspecified which of the
+    /// equal ones is returned).
+    pub fn best_match<'a>(
+        &self,
+        versions: impl IntoIterator<Item = &'a Version>,
+        alg: MatchingAlg,
+        pref: Preference,
+    ) -> Option<&'a Version> {
+        let matched = versions
+            .into_iter()
+            .filter(|ver| self.is_matched_by(alg, ver));
+
+        match pref {
+            Preference::Highest => matched.max(),
+            Preference::Lowest => matched.min(),
+            Preference::Stable => matched.max_by_key(|ver| (ver.pre_release.is_none(), *ver)),
+            Preference::Locked(locked) => matched.max_by_key(|ver| (*ver == locked, *ver)),
+        }
+    }
+}
+
+#[test]
+fn test_best_match() {
+    use MatchingAlg::*;
+
+    let versions = [
+        "1.0.0",
+        "1.2.3",
+        "1.5.0-rc.1",
+        "1.4.2",
+        "2.0.0-beta",
+        "2.0.0-rc",
+        "3.0.0",
+    ]
+    .map(|s| Version::from(s).unwrap());
+    let test = |s, alg, pref| {
+        Range::from(s)
+            .unwrap()
+            .best_match(&versions, alg, pref)
+            .map(|v| v.to_string())
+    };
+    let locked = Version::new(1, 2, 3);
+
+    assert_eq!(
+        Some("1.5.0-rc.1".to_string()),
+        test("^1.0.0", Classic, Preference::Highest)
+    );
+    assert_eq!(
+        Some("1.4.2".to_string()),
+        test("^1.0.0", Node, Preference::Highest)
+    );
+    assert_eq!(
+        Some("1.0.0".to_string()),
+        test("^1.0.0", Classic, Preference::Lowest)
+    );
+    assert_eq!(
+        Some("1.4.2".to_string()),
+        test("^1.0.0", Classic, Preference::Stable)
+    );
+    assert_eq!(
+        Some("2.0.0-rc".to_string()),
+        test(">=2.0.0-0 <3.0.0", Classic, Preference::Stable)
+    );
+    assert_eq!(
+        Some("1.2.3".to_string()),
+        test("^1.0.0", Classic, Preference::Locked(&locked))
+    );
+    assert_eq!(
+        Some("3.0.0".to_string()),
+        test(">=1.4.0", Classic, Preference::Locked(&locked))
+    );
+    assert_eq!(None, test("^4.0.0", Classic, Preference::Highest));
+    assert_eq!(None, test("^4.0.0", Classic, Preference::Stable));
+}
+
+#[test]
+fn test_max_min_satisfying() {
+    let versions = ["1.2.3", "1.2.4", "1.3.0", "2.0.0"].map(|s| Version::from(s).unwrap());
+    let range = Range::from("~1.2").unwrap();
+
+    assert_eq!(
+        Some(&versions[1]),
+        range.max_satisfying(versions.iter(), MatchingAlg::Node)
+    );
+    assert_eq!(
+        Some(&versions[0]),
+        range.min_satisfying(&versions, MatchingAlg::Node)
+    );
+    assert_eq!(
+        None,
+        Range::from("^3.0.0")
+            .unwrap()
+            .max_satisfying(&versions, MatchingAlg::Node)
+    );
+}
@@ -0,0 +1,299 @@
+use super::group;
+use super::interval::Interval;
+use super::matcher::MatchingAlg;
+use super::Range;
+use crate::version::Version;
+
+/// The versions matched by a range with a particular `MatchingAlg`, as sorted intervals that neither overlap nor touch.
+///
+/// With `MatchingAlg::Node`, a unit group matches the versions without pre-release tags within its interval, and
+/// possibly the pre-release versions of a single core (see `group::node_pre_release_core`), so these are kept apart.
+///
+/// It's built with `Range::to_version_set`, and is meant for matching many versions against the same range,
+/// since `contains` is a binary search rather than a walk through every unit of the range.
+///
+/// Sets are equal if they're built with the same `MatchingAlg` and contain the same versions.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct VersionSet {
+    alg: MatchingAlg,
+    /// With `MatchingAlg::Node`, only the versions without pre-release tags are matched within these intervals,
+    /// which start and end at such versions as well.
+    intervals: Vec<Interval>,
+    /// Intervals of pre-release versions matched with `MatchingAlg::Node`, each one within a single core.
+    pre_release_intervals: Vec<Interval>,
+}
+
+/// Turns `ver` into the version without pre-release tag that directly follows all pre-release versions of its core.
+fn without_pre_release(ver: &Version) -> Version {
+    Version {
+        core: ver.core.clone(),
+        pre_release: None,
+        build: None,
+    }
+}
+
+/// Narrows `interval` down to the versions without pre-release tags that lie within it, keeping it half-open.
+///
+/// No version without a pre-release tag lies between `1.2.3-rc` and `1.2.3`.
+fn without_pre_releases(interval: Interval) -> Interval {
+    Interval {
+        start: match interval.start.pre_release {
+            Some(_) => without_pre_release(&interval.start),
+            None => interval.start,
+        },
+        end: interval.end.as_ref().map(without_pre_release),
+    }
+}
+
+/// Sorts the intervals, merging the ones that overlap or touch and dropping the empty ones.
+fn merge(mut intervals: Vec<Interval>) -> Vec<Interval> {
+    intervals.retain(|interval| !interval.is_empty());
+    intervals.sort();
+
+    let mut out = Vec::<Interval>::with_capacity(intervals.len());
+
+    for interval in intervals {
+        match out.last_mut().and_then(|last| last.hull(&interval)) {
+            Some(hull) => *out.last_mut().unwrap() = hull,
+            None => out.push(interval),
+        }
+    }
+
+    out
+}
+
+/// Checks whether `interval` lies within one of the sorted disjoint `intervals`.
+fn covers(intervals: &[Interval], interval: &Interval) -> bool {
+    match intervals.partition_point(|i| i.start <= interval.start) {
+        0 => interval.is_empty(),
+        idx => intervals[idx - 1].contains(interval),
+    }
+}
+
+/// Checks whether `ver` lies within one of the sorted disjoint `intervals`.
+fn includes(intervals: &[Interval], ver: &Version) -> bool {
+    match intervals.partition_point(|i| i.start <= *ver) {
+        0 => false,
+        idx => intervals[idx - 1].end.as_ref().is_none_or(|end| ver < end),
+    }
+}
+
+/// Intersects every interval of the sorted disjoint `intervals` with every interval of `other`.
+fn intersect_all(intervals: &[Interval], other: &[Interval]) -> Vec<Interval> {
+    merge(
+        intervals
+            .iter()
+            .flat_map(|interval| other.iter().map(|i| interval.intersect(i)))
+            .collect(),
+    )
+}
+
+/// Checks whether any interval of the sorted disjoint `intervals` has common versions with `interval`.
+fn overlaps(intervals: &[Interval], interval: &Interval) -> bool {
+    let idx =
+        intervals.partition_point(|i| i.end.as_ref().is_some_and(|end| *end <= interval.start));
+
+    intervals
+        .get(idx)
+        .is_some_and(|i| !i.intersect(interval).is_empty())
+}
+
+impl VersionSet {
+    pub(crate) fn new(range: &Range, alg: MatchingAlg) -> Self {
+        let unit_groups = range.unit_groups();
+        let intervals = unit_groups.iter().map(|units| group::to_interval(units));
+
+        match alg {
+            MatchingAlg::Classic => Self {
+                alg,
+                intervals: merge(intervals.collect()),
+                pre_release_intervals: vec![],
+            },
+            MatchingAlg::Node => Self {
+                alg,
+                intervals: merge(intervals.map(without_pre_releases).collect()),
+                pre_release_intervals: merge(
+                    unit_groups
+                        .iter()
+                        .filter_map(|units| {
+                            let core = group::node_pre_release_core(units)?;
+
+                            Some(
+                                group::to_interval(units).intersect(&Interval::pre_releases(&core)),
+                            )
+                        })
+                        .collect(),
+                ),
+            },
+        }
+    }
+
+    /// The algorithm that the set was built with.
+    pub fn alg(&self) -> MatchingAlg {
+        self.alg
+    }
+
+    /// Checks whether `ver` is in the set, which is the same as matching it against the range the set was built from.
+    pub fn contains(&self, ver: &Version) -> bool {
+        match (self.alg, &ver.pre_release) {
+            (MatchingAlg::Node, Some(_)) => includes(&self.pre_release_intervals, ver),
+            _ => includes(&self.intervals, ver),
+        }
+    }
+
+    /// Checks whether the set has no versions at all.
+    pub fn is_empty(&self) -> bool {
+        self.intervals.is_empty() && self.pre_release_intervals.is_empty()
+    }
+
+    /// Finds the versions that are in both `self` and `other`.
+    ///
+    /// Panics if the sets were built with different `MatchingAlg`s.
+    pub fn intersect(&self, other: &Self) -> Self {
+        assert_eq!(self.alg, other.alg, "sets must use the same MatchingAlg");
+
+        Self {
+            alg: self.alg,
+            intervals: intersect_all(&self.intervals, &other.intervals),
+            pre_release_intervals: intersect_all(
+                &self.pre_release_intervals,
+                &other.pre_release_intervals,
+            ),
+        }
+    }
+
+    /// Finds the versions that are in either `self` or `other`.
+    ///
+    /// Panics if the sets were built with different `MatchingAlg`s.
+    pub fn union(&self, other: &Self) -> Self {
+        assert_eq!(self.alg, other.alg, "sets must use the same MatchingAlg");
+
+        let concat = |a: &[Interval], b: &[Interval]| merge([a, b].concat());
+
+        Self {
+            alg: self.alg,
+            intervals: concat(&self.intervals, &other.intervals),
+            pre_release_intervals: concat(
+                &self.pre_release_intervals,
+                &other.pre_release_intervals,
+            ),
+        }
+    }
+
+    /// Checks whether every version in `self` is also in `other`.
+    ///
+    /// Panics if the sets were built with different `MatchingAlg`s.
+    pub fn is_subset_of(&self, other: &Self) -> bool {
+        assert_eq!(self.alg, other.alg, "sets must use the same MatchingAlg");
+
+        self.intervals
+            .iter()
+            .all(|interval| covers(&other.intervals, interval))
+            && self
+                .pre_release_intervals
+                .iter()
+                .all(|interval| covers(&other.pre_release_intervals, interval))
+    }
+
+    /// Checks whether any version is in both `self` and `other`.
+    ///
+    /// Panics if the sets were built with different `MatchingAlg`s.
+    pub fn intersects(&self, other: &Self) -> bool {
+        assert_eq!(self.alg, other.alg, "sets must use the same MatchingAlg");
+
+        self.intervals
+            .iter()
+            .any(|interval| overlaps(&other.intervals, interval))
+            || self
+                .pre_release_intervals
+                .iter()
+                .any(|interval| overlaps(&other.pre_release_intervals, interval))
+    }
+
+    /// Checks whether any version in the set lies within `interval`, which is seen by `MatchingAlg::Classic`.
+    pub(crate) fn intersects_interval(&self, interval: &Interval) -> bool {
+        let outer = match self.alg {
+            MatchingAlg::Classic => interval.clone(),
+            MatchingAlg::Node => without_pre_releases(interval.clone()),
+        };
+
+        overlaps(&self.intervals, &outer) || overlaps(&self.pre_release_intervals, interval)
+    }
+
+    /// Finds the smallest version in the set, which is where its first interval starts.
+    pub(crate) fn first(&self) -> Option<&Version> {
+        let first = self.intervals.first().map(|i| &i.start);
+        let first_pre_release = self.pre_release_intervals.first().map(|i| &i.start);
+
+        match (first, first_pre_release) {
+            (Some(a), Some(b)) => Some(a.min(b)),
+            (a, b) => a.or(b),
+        }
+    }
+}
+
+#[test]
+fn test_new() {
+    let test = |s, alg| {
+        let set = VersionSet::new(&Range::from(s).unwrap(), alg);
+        let to_strings = |intervals: Vec<Interval>| {
+            intervals
+                .into_iter()
+                .map(|i| match i.end {
+                    Some(end) => format!("[{}, {})", i.start, end),
+                    None => format!("[{}, ∞)", i.start),
+                })
+                .collect::<Vec<_>>()
+        };
+
+        (
+            to_strings(set.intervals),
+            to_strings(set.pre_release_intervals),
+        )
+    };
+    let strings = |s: &[&str]| s.iter().map(|s| s.to_string()).collect::<Vec<_>>();
+
+    assert_eq!(
+        (
+            strings(&["[1.2.3, 2.0.0-0)", "[3.0.0, 4.0.0-0)", "[4.0.0, ∞)"]),
+            strings(&[])
+        ),
+        test("^3.0.0 || ^1.2.3 || >=4.0.0", MatchingAlg::Classic)
+    );
+    assert_eq!(
+        (strings(&["[1.2.3, 2.0.0)", "[3.0.0, ∞)"]), strings(&[])),
+        test("^3.0.0 || ^1.2.3 || >=4.0.0", MatchingAlg::Node)
+    );
+    assert_eq!(
+        (strings(&[]), strings(&["[1.2.3-alpha, 1.2.3-rc)"])),
+        test(">=1.2.3-alpha <1.2.3-rc", MatchingAlg::Node)
+    );
+    assert_eq!(
+        (
+            strings(&["[1.2.3, 1.2.4)"]),
+            strings(&["[1.2.3-alpha, 1.2.3-rc.0)"])
+        ),
+        test(
+            ">=1.2.3-alpha <=1.2.3-rc || >1.2.3-rc <=1.2.3",
+            MatchingAlg::Node
+        )
+    );
+}
+
+#[test]
+fn test_contains() {
+    super::testing::assert_for_range_pairs(
+        |a, b, alg| {
+            let (set, other) = (a.to_version_set(alg), b.to_version_set(alg));
+            let (both, either) = (set.intersect(&other), set.union(&other));
+
+            assert_eq!(set.intersects(&other), !both.is_empty(), "{} ∩ {}", a, b);
+            (set, both, either)
+        },
+        |(set, both, either), _, ver, in_a, in_b| {
+            set.contains(ver) == in_a
+                && both.contains(ver) == (in_a && in_b)
+                && either.contains(ver) == (in_a || in_b)
+        },
+    );
+}
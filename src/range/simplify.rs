@@ -0,0 +1,197 @@
+use super::bound::RangeBound;
+use super::comparator::RangeComparator;
+use super::matcher::MatchingAlg;
+use super::sugar::RangeSugar;
+use super::unit::{ParsedComparator, RangeUnit};
+use super::Range;
+use crate::version::Version;
+
+/// Drops the build metadata of `ver`, which ranges ignore anyway.
+fn without_build(ver: &Version) -> Version {
+    Version {
+        build: None,
+        ..ver.clone()
+    }
+}
+
+/// Lists the units that may describe the versions `sorted[first..=last]` (`last` being `None` if these are the largest
+/// ones), shortest first.
+fn candidates(sorted: &[&Version], first: usize, last: Option<usize>) -> Vec<RangeUnit> {
+    use ParsedComparator::*;
+    use RangeComparator::*;
+
+    let min = without_build(sorted[first]);
+
+    // a single version is clearer than a (possibly shorter) range that only happens to match it, e.g. `~1.0`
+    if last == Some(first) {
+        return vec![RangeUnit::from_version(None, min)];
+    }
+
+    let mut out = vec![
+        RangeUnit::from_version(Some(Caret), min.clone()),
+        RangeUnit::from_version(Some(Tilde), min.clone()),
+    ];
+
+    match last {
+        Some(last) => {
+            let max = without_build(sorted[last]);
+
+            if first == 0 {
+                out.push(RangeUnit::from_version(
+                    Some(Simple(LessOrEqual)),
+                    max.clone(),
+                ));
+            }
+
+            out.push(
+                RangeUnit::new(
+                    RangeBound(GreaterOrEqual, min),
+                    Some(RangeBound(LessOrEqual, max)),
+                )
+                .with_sugar(RangeSugar::Hyphen),
+            );
+        }
+        None if first == 0 => out.push(
+            RangeUnit::new(RangeBound(GreaterOrEqual, Version::new(0, 0, 0)), None)
+                .with_sugar(RangeSugar::XRange),
+        ),
+        None => out.push(RangeUnit::from_version(Some(Simple(GreaterOrEqual)), min)),
+    }
+
+    // the order is kept for candidates of the same length, so caret is preferred to tilde
+    out.sort_by_cached_key(|unit| unit.to_sugared_string().len());
+    out
+}
+
+impl Range {
+    /// Finds the shortest range that matches exactly the same versions out of `published` as `self` with the given
+    /// `alg`, following `node-semver`'s `simplifyRange` (e.g. `1.2.3 || 1.2.4 || 1.2.5 || ^1.3.0` becomes `^1.2.3`
+    /// if there's no `2.x` version).
+    ///
+    /// A run of consecutive matched versions is described with the version itself if it's a single one, or otherwise
+    /// with whichever of `*`, caret, tilde, hyphen, `<=` or `>=` is shortest and matches that run only, and keeps that
+    /// syntax (see `to_sugared_string`).
+    /// If the result isn't shorter than `self`, then `self` is returned as is.
+    pub fn simplify(&self, published: &[Version], alg: MatchingAlg) -> Self {
+        let mut sorted = published.iter().collect::<Vec<_>>();
+        sorted.sort();
+        sorted.dedup_by(|a, b| a == b);
+
+        let is_matched = sorted
+            .iter()
+            .map(|ver| self.is_matched_by(alg, ver))
+            .collect::<Vec<_>>();
+
+        let mut runs = vec![];
+        let mut first = None;
+
+        for (idx, &is_matched) in is_matched.iter().enumerate() {
+            match (first, is_matched) {
+                (None, true) => first = Some(idx),
+                (Some(start), false) => {
+                    runs.push((start, Some(idx - 1)));
+                    first = None;
+                }
+                _ => {}
+            }
+        }
+
+        runs.extend(first.map(|start| (start, None)));
+
+        let mut unit_groups = Vec::<Vec<RangeUnit>>::new();
+
+        for (first, last) in runs {
+            let end = last.map_or(sorted.len(), |last| last + 1);
+            let is_in_run = |idx: usize| first <= idx && idx < end;
+
+            let found = candidates(&sorted, first, last)
+                .into_iter()
+                .find_map(|unit| {
+                    let range = Self::Just(unit);
+                    let is_exact = sorted
+                        .iter()
+                        .enumerate()
+                        .all(|(idx, ver)| range.is_matched_by(alg, ver) == is_in_run(idx));
+
+                    is_exact.then_some(range)
+                });
+
+            match found {
+                Some(range) => {
+                    unit_groups.extend(range.unit_groups().into_iter().map(<[_]>::to_vec));
+                }
+                // e.g. with `MatchingAlg::Node`, `1.0.0 - 1.2.3-rc` doesn't match `1.2.3-rc`, so every version is listed
+                None => {
+                    unit_groups.extend(
+                        sorted[first..end]
+                            .iter()
+                            .map(|&ver| vec![RangeUnit::from_version(None, ver.clone())]),
+                    );
+                }
+            }
+        }
+
+        let simplified = Self::from_groups(unit_groups);
+
+        if simplified.to_sugared_string().len() < self.to_sugared_string().len() {
+            simplified
+        } else {
+            self.clone()
+        }
+    }
+}
+
+#[test]
+fn test_simplify() {
+    use MatchingAlg::*;
+
+    let published = [
+        "1.0.0", "1.1.0", "1.2.3", "1.2.4", "1.2.5", "1.3.0", "1.4.0", "2.0.0", "2.1.0", "3.0.0",
+    ]
+    .map(|s| Version::from(s).unwrap());
+    let test = |s, published: &[Version], alg| {
+        Range::from(s)
+            .unwrap()
+            .simplify(published, alg)
+            .to_sugared_string()
+    };
+
+    for alg in [Classic, Node] {
+        let test = |s| test(s, &published, alg);
+
+        assert_eq!("^1.2.3", test("1.2.3 || 1.2.4 || 1.2.5 || ^1.3.0"));
+        assert_eq!("*", test(">=0.1.0 || 1.0.0"));
+        assert_eq!("<=1.1.0", test("1.0.0 || 1.1.0"));
+        assert_eq!("~1.2.3", test(">=1.2.3 <=1.2.5 || 1.2.4"));
+        assert_eq!("1.4.0 - 2.1.0", test("1.4.0 || 2.0.0 || 2.1.0"));
+        assert_eq!(">=2.0.0", test("2.0.0 || ^2.1.0 || >=3.0.0"));
+        assert_eq!("1.2.3", test("1.2.3"));
+        assert_eq!("1.0.0 || ~1.2.4", test("1.0.0 || 1.2.4 || 1.2.5"));
+        // nothing's shorter than the original range
+        assert_eq!("^1.0.0", test("^1.0.0"));
+    }
+
+    let published =
+        ["1.2.3", "1.3.0", "1.4.0-rc", "1.4.0", "2.0.0"].map(|s| Version::from(s).unwrap());
+
+    // `1.4.0-rc` is only matched with `Classic`
+    assert_eq!("^1.2.3", test("1.2.3 || ^1.3.0", &published, Classic));
+    assert_eq!(
+        "<=1.3.0 || 1.4.0",
+        test("1.2.3 || 1.3.0 || 1.4.0", &published, Node)
+    );
+    assert_eq!(
+        "1.3.0 || 1.4.0-rc || 1.4.0",
+        test(">=1.3.0 <1.4.0-0 || 1.4.0-rc || 1.4.0", &published, Node)
+    );
+}
+
+#[test]
+fn test_simplify_matches() {
+    let published = super::testing::versions();
+
+    super::testing::assert_for_ranges(
+        |range, alg| range.simplify(&published, alg),
+        |simplified, alg, ver, matched| simplified.is_matched_by(alg, ver) == matched,
+    );
+}
@@ -0,0 +1,79 @@
+use super::matcher::MatchingAlg;
+use super::set::VersionSet;
+use super::Range;
+
+impl Range {
+    /// Checks whether every version matched by `self` is also matched by `other` with the given `alg`,
+    /// following `node-semver`'s `subset` (e.g. `~1.2.3` is a subset of `^1.0.0`).
+    ///
+    /// This is decided by comparing the bounds of both ranges, without enumerating any versions.
+    /// A range that doesn't match anything is a subset of every range.
+    pub fn is_subset_of(&self, other: &Self, alg: MatchingAlg) -> bool {
+        VersionSet::new(self, alg).is_subset_of(&VersionSet::new(other, alg))
+    }
+}
+
+#[test]
+fn test_is_subset_of() {
+    use MatchingAlg::*;
+
+    let test = |a, b, alg| {
+        Range::from(a)
+            .unwrap()
+            .is_subset_of(&Range::from(b).unwrap(), alg)
+    };
+
+    for alg in [Classic, Node] {
+        assert!(test("~1.2.3", "^1.0.0", alg));
+        assert!(!test("^1.0.0", "~1.2.3", alg));
+        assert!(test("1.2.3", "^1.0.0", alg));
+        assert!(test("^1.2.3", "*", alg));
+        assert!(test("^1.2.3", "^1.2.3", alg));
+        assert!(test("^1.2.3 || ^2.0.0", ">=1.0.0", alg));
+        assert!(!test("^1.2.3 || ^2.0.0", "^1.0.0", alg));
+        assert!(test("~1.2.3", "^1.0.0 || ^2.0.0", alg));
+        assert!(test(
+            ">=1.0.0 <1.5.0 || >=1.5.0 <2.0.0",
+            ">=1.0.0 <2.0.0",
+            alg
+        ));
+        assert!(test("<0.0.0-0", "1.2.3", alg));
+        assert!(test(">=2.0.0 <1.0.0", "1.2.3", alg));
+        assert!(!test("*", "<0.0.0-0", alg));
+    }
+
+    // pre-release versions of `1.1.0` aren't matched by the right-hand side
+    assert!(!test("1.x", "~1.0 || >=1.1.0 <2.0.0-0", Classic));
+    assert!(test("1.x", "~1.0 || >=1.1.0 <2.0.0-0", Node));
+    // with `Node`, `1.2.3-beta` is only matched by the left-hand side
+    assert!(test(
+        ">=1.2.3-alpha <1.2.3",
+        ">=1.2.3-0 <1.2.3 >=1.0.0",
+        Node
+    ));
+    assert!(!test(
+        ">=1.2.3-alpha <1.2.3-rc",
+        ">=1.2.3-0 <1.2.3 >=1.0.0",
+        Node
+    ));
+    assert!(test(
+        ">=1.2.3-alpha <1.2.3-rc",
+        ">=1.2.3-0 <1.2.3 >=1.0.0",
+        Classic
+    ));
+    assert!(test(
+        ">=1.2.3-alpha <1.2.3-rc",
+        ">=1.2.3-0 <1.2.3-rc.0",
+        Node
+    ));
+    assert!(test("^1.2.3-beta", "^1.2.3", Node));
+    assert!(!test("^1.2.3-beta", "^1.2.3", Classic));
+}
+
+#[test]
+fn test_is_subset_of_matches() {
+    super::testing::assert_for_range_pairs(
+        |a, b, alg| a.is_subset_of(b, alg),
+        |&is_subset, _, _, in_a, in_b| !is_subset || !in_a || in_b,
+    );
+}
@@ -0,0 +1,265 @@
+use super::bound::RangeBound;
+use super::comparator::RangeComparator;
+use super::unit::{ParsedComparator, RangeUnit};
+use super::Range;
+use crate::version::pre_release::VersionPreRelease;
+use crate::version::Version;
+
+/// The syntax that a `RangeUnit` is written in.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum RangeSugar {
+    /// A version with an optional comparator, e.g. `>=1.2.3` or `1.2.3`.
+    Comparator,
+    /// E.g. `^1.2.3`, `^0.x` or `^*`.
+    Caret,
+    /// E.g. `~1.2.3`, `~1.2` or `~>1.2`.
+    Tilde,
+    /// A partial version with an optional comparator, e.g. `1.x`, `*` or `<1.2`.
+    XRange,
+    /// E.g. `1.2.3 - 4.5.6`.
+    Hyphen,
+}
+
+/// Formats `ver` as the x-range it starts (`1.0.0` as `1.x`, `1.2.0` as `1.2.x`), if it does.
+fn x_ranges(ver: &Version) -> Vec<String> {
+    let core = &ver.core;
+
+    match (&ver.pre_release, core.minor, core.patch) {
+        (None, 0, 0) => vec![format!("{}.x", core.major), format!("{}.0.x", core.major)],
+        (None, minor, 0) => vec![format!("{}.{}.x", core.major, minor)],
+        _ => vec![],
+    }
+}
+
+/// Formats the partial version whose versions all lie below `ver`, but only if `ver` directly follows them
+/// (e.g. `1.3.0-0` follows `1.2.x` and `2.0.0-0` follows `1.x`).
+fn x_ranges_before(ver: &Version) -> Vec<String> {
+    let core = &ver.core;
+
+    if ver.pre_release != Some(VersionPreRelease::default()) || core.patch != 0 {
+        return vec![];
+    }
+
+    match (core.major, core.minor) {
+        (0, 0) => vec![],
+        (major, 0) => vec![format!("{}.x", major - 1)],
+        (major, minor) => vec![format!("{}.{}.x", major, minor - 1)],
+    }
+}
+
+impl RangeUnit {
+    /// Lists the strings that may parse back into this unit, in order of preference.
+    fn sugar_candidates(&self) -> Vec<(RangeSugar, String)> {
+        use RangeComparator::*;
+        use RangeSugar::*;
+
+        let mut out = vec![(Comparator, self.to_string())];
+
+        match (&self.bound, &self.extra_bound) {
+            (RangeBound(GreaterOrEqual, lower), upper) => {
+                out.push((Caret, format!("^{}", lower)));
+                out.extend(x_ranges(lower).into_iter().map(|s| (XRange, s)));
+                out.push((Tilde, format!("~{}", lower)));
+
+                // partial tildes (e.g. `~1.2`) are only used for units built with a tilde, otherwise `1.2.x` is clearer
+                if self.sugar == Some(Tilde) && lower.pre_release.is_none() && lower.core.patch == 0
+                {
+                    out.push((Tilde, format!("~{}.{}", lower.core.major, lower.core.minor)));
+
+                    if lower.core.minor == 0 {
+                        out.push((Tilde, format!("~{}", lower.core.major)));
+                    }
+                }
+
+                // partial carets (e.g. `^0.x` or `^*`) are only used for the bounds that a caret on the full version can't describe
+                let caret = RangeUnit::from_version(Some(ParsedComparator::Caret), lower.clone());
+
+                if self.sugar == Some(Caret) && caret != *self && lower.pre_release.is_none() {
+                    let core = &lower.core;
+
+                    match upper {
+                        None if *lower == Version::new(0, 0, 0) => {
+                            out.push((Caret, "^*".to_string()))
+                        }
+                        Some(_) if core.patch == 0 => {
+                            out.push((Caret, format!("^{}.{}", core.major, core.minor)));
+
+                            if core.minor == 0 {
+                                out.push((Caret, format!("^{}", core.major)));
+                            }
+                        }
+                        _ => {}
+                    }
+                }
+
+                match upper {
+                    None if *lower == Version::new(0, 0, 0) => out.push((XRange, "*".to_string())),
+                    Some(RangeBound(LessOrEqual, upper)) => {
+                        out.push((Hyphen, format!("{} - {}", lower, upper)))
+                    }
+                    Some(RangeBound(Less, upper)) => out.extend(
+                        x_ranges_before(upper)
+                            .into_iter()
+                            .map(|s| (Hyphen, format!("{} - {}", lower, s))),
+                    ),
+                    _ => {}
+                }
+            }
+            (RangeBound(Less, upper), None) => {
+                let core = &upper.core;
+
+                if x_ranges_before(upper).is_empty() {
+                    return out;
+                }
+
+                out.push((XRange, format!("<{}.{}", core.major, core.minor)));
+
+                if core.minor == 0 {
+                    out.push((XRange, format!("<{}", core.major)));
+                }
+            }
+            _ => {}
+        }
+
+        out
+    }
+
+    /// Formats the unit in the shortest syntax that parses back into it, preferring the one it was parsed from.
+    pub(crate) fn to_sugared_string(&self) -> String {
+        let mut candidates = self.sugar_candidates();
+
+        // sorting is stable, so the candidates of the same length keep their order
+        candidates.sort_by_key(|(sugar, s)| (Some(*sugar) != self.sugar, s.len()));
+
+        candidates
+            .into_iter()
+            .map(|(_, s)| s)
+            .find(|s| {
+                matches!(RangeUnit::parse(s), Ok((unit, ""))
+                    if unit.bound == self.bound && unit.extra_bound == self.extra_bound)
+            })
+            .unwrap_or_else(|| self.to_string())
+    }
+
+    /// The syntax that the unit was parsed from, or `None` if it was built some other way (e.g. by `Range::normalize`).
+    pub fn sugar(&self) -> Option<RangeSugar> {
+        self.sugar
+    }
+}
+
+#[test]
+fn test_unit_to_sugared_string() {
+    use RangeComparator::*;
+
+    let test = |s| RangeUnit::parse(s).expect(s).0.to_sugared_string();
+
+    assert_eq!("^1.2.3", test("^1.2.3"));
+    assert_eq!("^1.2.3-rc.1", test("^1.2.3-rc.1"));
+    assert_eq!("^0.0.1", test("^0.0.1"));
+    assert_eq!("~1.2.3", test("~1.2.3"));
+    assert_eq!("~1.2", test("~1.2"));
+    assert_eq!("~1.2", test("~1.2.0"));
+    assert_eq!("~1", test("~1"));
+    assert_eq!("~1.2", test("~>1.2"));
+    assert_eq!("*", test("~*"));
+    assert_eq!("^1.2.0", test("^1.2"));
+    assert_eq!("^1.0.0", test("^1.x"));
+    assert_eq!("^0.0", test("^0.0"));
+    assert_eq!("^0", test("^0.x"));
+    assert_eq!("^*", test("^*"));
+    assert_eq!("^0.0.0", test("^0.0.0"));
+    assert_eq!("1.x", test("1.x"));
+    assert_eq!("1.x", test("1"));
+    assert_eq!("1.2.x", test("1.2.*"));
+    assert_eq!("*", test("*"));
+    assert_eq!("*", test("x"));
+    assert_eq!("<1.2", test("<1.2"));
+    assert_eq!("<1", test("<1.x"));
+    assert_eq!("<1.3", test("<=1.2"));
+    assert_eq!(">=1.3.0", test(">1.2"));
+    assert_eq!("1.2.3 - 4.5.6", test("1.2.3 - 4.5.6"));
+    assert_eq!("1.2.3 - 4.5.x", test("1.2.3 - 4.5"));
+    assert_eq!("1.2.3 - 4.x", test("1.2.3 - 4"));
+    assert_eq!("1.2.0 - 3.4.5", test("1.2 - 3.4.5"));
+    assert_eq!(">=1.2.3", test("1.2.3 - *"));
+    assert_eq!(">=1.2.3", test(">=1.2.3"));
+    assert_eq!("<2.0.0-0", test("<2.0.0-0"));
+    assert_eq!("1.2.3", test("=1.2.3"));
+    assert_eq!(">1.2.3-rc", test(">1.2.3-rc"));
+
+    // built without any sugar
+    let unit = |lower: &str, upper: Option<(RangeComparator, &str)>| {
+        RangeUnit::new(
+            RangeBound(GreaterOrEqual, Version::from(lower).unwrap()),
+            upper.map(|(comp, s)| RangeBound(comp, Version::from(s).unwrap())),
+        )
+        .to_sugared_string()
+    };
+
+    assert_eq!("^1.2.3", unit("1.2.3", Some((Less, "2.0.0-0"))));
+    assert_eq!("^0.1.2", unit("0.1.2", Some((Less, "0.2.0-0"))));
+    assert_eq!("1.x", unit("1.0.0", Some((Less, "2.0.0-0"))));
+    assert_eq!("~1.2.3", unit("1.2.3", Some((Less, "1.3.0-0"))));
+    assert_eq!("1.2.3 - 2.0.0", unit("1.2.3", Some((LessOrEqual, "2.0.0"))));
+    assert_eq!("*", unit("0.0.0", None));
+    assert_eq!(">=1.2.3 <2.0.0", unit("1.2.3", Some((Less, "2.0.0"))));
+}
+
+impl Range {
+    /// Formats the range in the syntax its units were parsed from (e.g. `^1.2.3 || 2.x`),
+    /// unlike `Display`, which always shows the bounds that they stand for (e.g. `>=1.2.3 <2.0.0-0 || >=2.0.0 <3.0.0-0`).
+    ///
+    /// Units that were built some other way (e.g. by `normalize`) are written in the shortest syntax that describes
+    /// the same bounds, so `Range::from` always parses the result back into a range with the same bounds.
+    pub fn to_sugared_string(&self) -> String {
+        self.unit_groups()
+            .into_iter()
+            .map(|units| {
+                units
+                    .iter()
+                    .map(RangeUnit::to_sugared_string)
+                    .collect::<Vec<_>>()
+                    .join(" ")
+            })
+            .collect::<Vec<_>>()
+            .join(" || ")
+    }
+}
+
+#[test]
+fn test_to_sugared_string() {
+    let test = |s| Range::from(s).unwrap().to_sugared_string();
+    let normalized = |s| Range::from(s).unwrap().normalize().to_sugared_string();
+
+    assert_eq!("^1.2.3", test("^1.2.3"));
+    assert_eq!("1.x || ^2.0.0-rc", test("1.x || ^2.0.0-rc"));
+    assert_eq!(
+        ">=1.2.3 <1.5 || 1.2.3 - 1.4.0",
+        test(">=1.2.3 <1.5 || 1.2.3 - 1.4.0")
+    );
+    assert_eq!("^1.2.0", normalized("^1.2.0 || ^1.5.0 || 1.9.x"));
+    assert_eq!("1.x || 3.x", normalized("^3.0.0 || 1.x"));
+    assert_eq!(
+        "~1.2.3",
+        Range::from("^1.2.3")
+            .unwrap()
+            .intersect(&Range::from("<1.3").unwrap())
+            .to_sugared_string()
+    );
+
+    for s in [
+        "^1.2.3 || ~1.2 || 1.x || * || >1.2.3 <=1.5.0",
+        "1.2.3 - 4.5 || <1 || >=0.0.0-0",
+        "^1.2 || ^0.x || ~>0.1 || ^*",
+        ">=1.2.3-alpha <1.2.3-rc >=1.0.0",
+    ] {
+        let range = Range::from(s).unwrap();
+
+        for range in [range.clone(), range.normalize(), range.complement()] {
+            assert_eq!(
+                range.to_string(),
+                Range::from(&range.to_sugared_string()).unwrap().to_string()
+            );
+        }
+    }
+}
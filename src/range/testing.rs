@@ -0,0 +1,125 @@
+use std::fmt;
+
+use super::matcher::MatchingAlg;
+use super::Range;
+use crate::version::Version;
+
+/// Ranges that the set operations are checked against each other with, covering groups that only match pre-release
+/// versions, groups that touch, and groups that can't match anything.
+const RANGES: [&str; 24] = [
+    "*",
+    ">=0.0.0",
+    "^1.2.3",
+    ">=1.2.3 <2.0.0-0",
+    "~1.2",
+    "~1.2 || 1.2.3-beta",
+    "1.2.3 || 1.2.4",
+    "1.2.3",
+    "<1.2.3",
+    ">=1.2.3-alpha <1.2.3",
+    ">=1.2.3-alpha <1.2.3-rc",
+    ">=1.2.3-0 <1.2.3-rc.0",
+    ">=1.2.3-rc <=1.2.3-rc || >=1.0.0 <1.2.3-alpha",
+    ">1.2.3-beta.1 || <1.0.0",
+    "<1.0.0 || >=1.0.0",
+    "1.x || >=2.5.0 <3.0.0-rc",
+    "<=1.2.3 || 2.0.0-rc.1",
+    ">2.0.0-0 <2.0.0",
+    "2.0.0-0",
+    "=1.2.3-beta",
+    ">=2.0.0 || <=2.0.0",
+    ">=2.0.0 <1.0.0",
+    ">=1.2.0 <1.4.5 || >=2.0.0 <2.1.3",
+    "0.9.0 || 1.0.0 || 1.2.2 || 1.2.4 || 1.9.9 || 2.0.0",
+];
+
+/// Versions that lie within, around, and between the bounds of `RANGES`.
+const VERSIONS: [&str; 21] = [
+    "0.0.0-0",
+    "0.9.0",
+    "1.0.0",
+    "1.2.2",
+    "1.2.3-alpha",
+    "1.2.3-beta",
+    "1.2.3-beta.2",
+    "1.2.3-rc",
+    "1.2.3",
+    "1.2.4",
+    "1.3.0-0",
+    "1.4.5",
+    "1.9.9",
+    "2.0.0-0",
+    "2.0.0-rc.1",
+    "2.0.0",
+    "2.1.2",
+    "2.1.3",
+    "2.5.0",
+    "3.0.0-rc",
+    "3.0.0",
+];
+
+/// Parses `VERSIONS`, which are sorted.
+pub(crate) fn versions() -> Vec<Version> {
+    VERSIONS.map(|s| Version::from(s).unwrap()).to_vec()
+}
+
+/// Checks `f(&out, alg, ver, matched)` for every range out of `RANGES`, `MatchingAlg` and version out of `VERSIONS`,
+/// where `out` is computed once per range and `alg` with `op`, and `matched` tells whether the range matches `ver`.
+pub(crate) fn assert_for_ranges<T: fmt::Debug>(
+    op: impl Fn(&Range, MatchingAlg) -> T,
+    f: impl Fn(&T, MatchingAlg, &Version, bool) -> bool,
+) {
+    let versions = versions();
+
+    for range in RANGES.map(|s| Range::from(s).unwrap()) {
+        for alg in [MatchingAlg::Classic, MatchingAlg::Node] {
+            let out = op(&range, alg);
+
+            for ver in &versions {
+                let matched = range.is_matched_by(alg, ver);
+
+                assert!(
+                    f(&out, alg, ver, matched),
+                    "{} => {:?} for {} with {:?}",
+                    range,
+                    out,
+                    ver,
+                    alg
+                );
+            }
+        }
+    }
+}
+
+/// Checks `f(&out, alg, ver, in_a, in_b)` for every pair of ranges out of `RANGES`, `MatchingAlg` and version out of
+/// `VERSIONS`, where `out` is computed once per pair and `alg` with `op`, and `in_a` and `in_b` tell whether each of
+/// the ranges matches `ver`.
+pub(crate) fn assert_for_range_pairs<T: fmt::Debug>(
+    op: impl Fn(&Range, &Range, MatchingAlg) -> T,
+    f: impl Fn(&T, MatchingAlg, &Version, bool, bool) -> bool,
+) {
+    let versions = versions();
+    let ranges = RANGES.map(|s| Range::from(s).unwrap());
+
+    for a in &ranges {
+        for b in &ranges {
+            for alg in [MatchingAlg::Classic, MatchingAlg::Node] {
+                let out = op(a, b, alg);
+
+                for ver in &versions {
+                    let (in_a, in_b) = (a.is_matched_by(alg, ver), b.is_matched_by(alg, ver));
+
+                    assert!(
+                        f(&out, alg, ver, in_a, in_b),
+                        "{}, {} => {:?} for {} with {:?}",
+                        a,
+                        b,
+                        out,
+                        ver,
+                        alg
+                    );
+                }
+            }
+        }
+    }
+}
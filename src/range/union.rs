@@ -0,0 +1,110 @@
+use super::group;
+use super::Range;
+
+impl Range {
+    /// Finds the range of versions that are matched by either `self` or `other`, with either `MatchingAlg`.
+    ///
+    /// The result is normalized, see `normalize`.
+    pub fn union(&self, other: &Self) -> Self {
+        let unit_groups = self
+            .unit_groups()
+            .into_iter()
+            .chain(other.unit_groups())
+            .map(<[_]>::to_vec)
+            .collect();
+
+        Self::from_groups(unit_groups).normalize()
+    }
+
+    /// Rewrites the range into the smallest equivalent set of unit groups, which are matched by the same versions
+    /// with either `MatchingAlg`.
+    ///
+    /// Every group is reduced to its tightest bounds and dropped if it can't match anything,
+    /// then the groups that overlap or touch (e.g. `^1.2.0 || ^1.5.0`) are merged, and the rest are sorted.
+    ///
+    /// Note that groups matching pre-release versions differently with `MatchingAlg::Node` are kept apart:
+    /// e.g. `<1.0.0 || >=1.0.0` stays as is, since `0.0.0-alpha` is only matched by `>=0.0.0-0`.
+    pub fn normalize(&self) -> Self {
+        let mut unit_groups = self
+            .unit_groups()
+            .into_iter()
+            .filter_map(group::normalize)
+            .collect::<Vec<_>>();
+
+        'merging: loop {
+            for idx in 0..unit_groups.len() {
+                for other_idx in (idx + 1)..unit_groups.len() {
+                    if let Some(units) = group::merge(&unit_groups[idx], &unit_groups[other_idx]) {
+                        unit_groups[idx] = units;
+                        unit_groups.remove(other_idx);
+
+                        continue 'merging;
+                    }
+                }
+            }
+
+            break;
+        }
+
+        unit_groups.sort_by_cached_key(|units| group::to_interval(units));
+
+        Self::from_groups(unit_groups)
+    }
+}
+
+#[test]
+fn test_normalize() {
+    let test = |s| Range::from(s).unwrap().normalize().to_string();
+
+    assert_eq!(">=1.2.0 <2.0.0-0", test("^1.2.0 || ^1.5.0 || 1.9.x"));
+    assert_eq!(">=1.0.0 <2.0.0", test(">=1.0.0 <1.5.0 || >=1.5.0 <2.0.0"));
+    assert_eq!("1.2.3 || 1.2.4", test("1.2.4 || 1.2.3 || 1.2.3"));
+    assert_eq!(">=1.2.3", test(">=1.0.0 >=1.2.3"));
+    assert_eq!(">=0.0.0", test("^1.2.3 || * || ~2.0"));
+    assert_eq!(
+        ">=1.0.0 <1.3.0-0 || >=2.0.0 <3.0.0-0",
+        test("^2.0.0 || ~1.2 || 1.0 || >=1.0.5 <1.2.0")
+    );
+    // pre-release versions of `1.1.0` fall in between
+    assert_eq!(">=1.0.0 <1.1.0-0 || >=1.1.0 <1.2.0-0", test("1.1 || 1.0"));
+    assert_eq!("<0.0.0-0", test(">=2.0.0 <1.0.0"));
+    assert_eq!(">=1.0.0", test(">=2.0.0 <1.0.0 || >=1.0.0"));
+    assert_eq!("<1.0.0 || >=1.0.0", test(">=1.0.0 || <1.0.0"));
+}
+
+#[test]
+fn test_union() {
+    let test = |a, b| {
+        Range::from(a)
+            .unwrap()
+            .union(&Range::from(b).unwrap())
+            .to_string()
+    };
+
+    assert_eq!(">=1.2.3 <3.0.0", test("^1.2.3", ">=2.0.0-0 <3.0.0"));
+    assert_eq!(
+        ">=1.2.3 <2.0.0-0 || >=2.0.0 <3.0.0-0",
+        test("^1.2.3", "^2.0.0")
+    );
+    assert_eq!(
+        ">=1.2.3 <2.0.0-0 || >=3.0.0 <4.0.0-0",
+        test("^3.0.0", "^1.2.3")
+    );
+    assert_eq!(">=1.0.0 <2.0.0-0", test("~1.2 || ~1.0", "1.x"));
+}
+
+#[test]
+fn test_normalize_matches() {
+    super::testing::assert_for_ranges(
+        |range, _| range.normalize(),
+        |normalized, alg, ver, matched| normalized.is_matched_by(alg, ver) == matched,
+    );
+}
+
+#[test]
+fn test_union_matches() {
+    super::testing::assert_for_range_pairs(
+        |a, b, _| a.union(b),
+        |either, alg, ver, in_a, in_b| either.is_matched_by(alg, ver) == (in_a || in_b),
+    );
+}
@@ -2,12 +2,14 @@ use std::fmt;
 
 use super::bound::RangeBound;
 use super::comparator::RangeComparator;
+use super::error::{RangeParseErrorKind, UnitParseError};
+use super::sugar::RangeSugar;
 use crate::version::difference::VersionDiff;
 use crate::version::pattern::VersionPattern;
 use crate::version::pre_release::VersionPreRelease;
 use crate::version::Version;
 
-enum ParsedComparator {
+pub(crate) enum ParsedComparator {
     Simple(RangeComparator),
     Caret,
     Tilde,
@@ -22,11 +24,44 @@ enum ParsedPart {
 pub struct RangeUnit {
     pub(crate) bound: RangeBound,
     pub(crate) extra_bound: Option<RangeBound>,
+    /// The syntax that the unit was parsed from or built with (e.g. by `Range::caret`), if any.
+    pub(crate) sugar: Option<RangeSugar>,
 }
 
 impl RangeUnit {
     pub(crate) fn new(bound: RangeBound, extra_bound: Option<RangeBound>) -> Self {
-        Self { bound, extra_bound }
+        Self {
+            bound,
+            extra_bound,
+            sugar: None,
+        }
+    }
+
+    pub(crate) fn with_sugar(mut self, sugar: RangeSugar) -> Self {
+        self.sugar = Some(sugar);
+        self
+    }
+
+    /// Lists the bounds that a version has to match in order to match the unit (e.g. `>=1.2.3` and `<2.0.0-0`
+    /// for `^1.2.3`).
+    pub fn bounds(&self) -> impl Iterator<Item = &RangeBound> {
+        std::iter::once(&self.bound).chain(self.extra_bound.as_ref())
+    }
+
+    /// Finds the bound that limits the unit from below, i.e. the one with `>`, `>=` or `=`.
+    pub fn lower(&self) -> Option<&RangeBound> {
+        use RangeComparator::*;
+
+        self.bounds()
+            .find(|RangeBound(comp, _)| matches!(comp, Greater | GreaterOrEqual | Equal))
+    }
+
+    /// Finds the bound that limits the unit from above, i.e. the one with `<`, `<=` or `=`.
+    pub fn upper(&self) -> Option<&RangeBound> {
+        use RangeComparator::*;
+
+        self.bounds()
+            .find(|RangeBound(comp, _)| matches!(comp, Less | LessOrEqual | Equal))
     }
 }
 
@@ -42,6 +77,33 @@ impl fmt::Display for RangeUnit {
     }
 }
 
+#[test]
+fn test_bounds() {
+    let parse = |s| RangeUnit::parse(s).expect(s).0;
+    let to_string = |bound: Option<&RangeBound>| bound.map(|b| b.to_string());
+
+    let unit = parse("^1.2.3");
+    assert_eq!(Some(">=1.2.3".to_string()), to_string(unit.lower()));
+    assert_eq!(Some("<2.0.0-0".to_string()), to_string(unit.upper()));
+    assert_eq!(2, unit.bounds().count());
+
+    let unit = parse("1.2.3");
+    assert_eq!(Some("1.2.3".to_string()), to_string(unit.lower()));
+    assert_eq!(Some("1.2.3".to_string()), to_string(unit.upper()));
+
+    let unit = parse(">1.2.3");
+    assert_eq!(
+        Some(RangeComparator::Greater),
+        unit.lower().map(|b| b.comparator())
+    );
+    assert_eq!(
+        Some(&Version::new(1, 2, 3)),
+        unit.lower().map(|b| b.version())
+    );
+    assert_eq!(None, unit.upper());
+    assert_eq!(None, parse("<1.2").lower());
+}
+
 #[test]
 fn test_to_string() {
     use RangeComparator::*;
@@ -61,7 +123,9 @@ fn test_to_string() {
 }
 
 impl RangeUnit {
-    pub(crate) fn parse(s: &str) -> Option<(Self, &str)> {
+    pub(crate) fn parse(s: &str) -> Result<(Self, &str), UnitParseError<'_>> {
+        use RangeParseErrorKind::*;
+
         let (comp, r) = Self::parse_comparator(s);
         let (part, r) = Self::parse_part(r)?;
 
@@ -69,13 +133,18 @@ impl RangeUnit {
             let (second_part, r) = Self::parse_part(r)?;
 
             if comp.is_some() {
-                return None;
+                return Err(UnitParseError::new(ComparatorBeforeHyphen, r));
             }
 
-            return Some((Self::merge_parts(part, second_part), r));
+            return Ok((
+                Self::merge_parts(part, second_part).with_sugar(RangeSugar::Hyphen),
+                r,
+            ));
         }
 
-        Self::from_part(comp, part).map(|u| (u, r))
+        Self::from_part(comp, part)
+            .map(|u| (u, r))
+            .map_err(|kind| UnitParseError::new(kind, r))
     }
 
     fn parse_comparator(s: &str) -> (Option<ParsedComparator>, &str) {
@@ -85,7 +154,7 @@ impl RangeUnit {
             (Some(Simple(comp)), r)
         } else if let Some(r) = s.strip_prefix('^') {
             (Some(Caret), r)
-        } else if let Some(r) = s.strip_prefix('~') {
+        } else if let Some(r) = s.strip_prefix("~>").or_else(|| s.strip_prefix('~')) {
             (Some(Tilde), r)
         } else {
             (None, s)
@@ -101,24 +170,50 @@ impl RangeUnit {
         )
     }
 
-    fn parse_part(s: &str) -> Option<(ParsedPart, &str)> {
-        if let Some((ver, r)) = Version::parse(s) {
-            Some((ParsedPart::Version(ver), r))
-        } else if let Some((pat, r)) = VersionPattern::parse(s) {
-            Some((ParsedPart::Pattern(pat), r))
+    fn parse_part(s: &str) -> Result<(ParsedPart, &str), UnitParseError<'_>> {
+        use RangeParseErrorKind::*;
+
+        let ver_err = match Version::parse(s) {
+            Ok((ver, r)) => return Ok((ParsedPart::Version(ver), r)),
+            Err(err) => err,
+        };
+
+        let pat_err = match VersionPattern::parse(s) {
+            // e.g. `1.2.x-beta`, which would otherwise fail on the tag as if it were the next unit
+            Ok((_, r)) if r.starts_with('-') => {
+                let end = r.find([' ', '|']).unwrap_or(r.len());
+
+                return Err(UnitParseError::new(PreReleaseOnPattern, &r[end..]));
+            }
+            Ok((pat, r)) => return Ok((ParsedPart::Pattern(pat), r)),
+            Err(err) => err,
+        };
+
+        // report whichever attempt got further into the input
+        let (kind, rest) = if pat_err.1.len() < ver_err.1.len() {
+            pat_err
         } else {
-            None
-        }
+            ver_err
+        };
+
+        Err(UnitParseError {
+            kind: InvalidUnit,
+            rest,
+            cause: Some(kind),
+        })
     }
 
-    fn from_part(comp: Option<ParsedComparator>, part: ParsedPart) -> Option<Self> {
+    fn from_part(
+        comp: Option<ParsedComparator>,
+        part: ParsedPart,
+    ) -> Result<Self, RangeParseErrorKind> {
         match part {
-            ParsedPart::Version(ver) => Some(Self::from_version(comp, ver)),
+            ParsedPart::Version(ver) => Ok(Self::from_version(comp, ver)),
             ParsedPart::Pattern(pat) => Self::from_pattern(comp, pat),
         }
     }
 
-    fn from_version(comp: Option<ParsedComparator>, mut ver: Version) -> Self {
+    pub(crate) fn from_version(comp: Option<ParsedComparator>, mut ver: Version) -> Self {
         use ParsedComparator::*;
         use RangeComparator::*;
         use VersionDiff::*;
@@ -126,15 +221,18 @@ impl RangeUnit {
         ver.build = None;
 
         match comp {
-            None => Self::new(RangeBound(Equal, ver), None),
-            Some(Simple(comp)) => Self::new(RangeBound(comp, ver), None),
+            None => Self::new(RangeBound(Equal, ver), None).with_sugar(RangeSugar::Comparator),
+            Some(Simple(comp)) => {
+                Self::new(RangeBound(comp, ver), None).with_sugar(RangeSugar::Comparator)
+            }
             Some(Tilde) => {
-                let upper_ver = ver.to_incremented(PreMinor);
+                let upper_ver = ver.to_upper_bound(PreMinor);
 
                 Self::new(
                     RangeBound(GreaterOrEqual, ver),
-                    Some(RangeBound(Less, upper_ver)),
+                    upper_ver.map(|ver| RangeBound(Less, ver)),
                 )
+                .with_sugar(RangeSugar::Tilde)
             }
             Some(Caret) => {
                 let diff = if ver.core.major == 0 && ver.core.minor == 0 {
@@ -145,68 +243,100 @@ impl RangeUnit {
                     PreMajor
                 };
 
-                let upper_ver = ver.to_incremented(diff);
+                let upper_ver = ver.to_upper_bound(diff);
 
                 Self::new(
                     RangeBound(GreaterOrEqual, ver),
-                    Some(RangeBound(Less, upper_ver)),
+                    upper_ver.map(|ver| RangeBound(Less, ver)),
                 )
+                .with_sugar(RangeSugar::Caret)
             }
         }
     }
 
-    fn from_pattern(comp: Option<ParsedComparator>, pat: VersionPattern) -> Option<Self> {
+    fn from_pattern(
+        comp: Option<ParsedComparator>,
+        pat: VersionPattern,
+    ) -> Result<Self, RangeParseErrorKind> {
         use ParsedComparator::*;
         use RangeComparator::*;
-
-        match (comp, pat.to_bounds()) {
-            (None, (lower, upper)) => Some(Self::new(
+        use RangeParseErrorKind::*;
+
+        let is_major = pat == VersionPattern::Major;
+        let sugar = match comp {
+            Some(Tilde) => RangeSugar::Tilde,
+            Some(Caret) => RangeSugar::Caret,
+            _ => RangeSugar::XRange,
+        };
+
+        // the upper bound is missing for `*` and for patterns that cover the largest representable versions
+        let unit = match (comp, pat.to_bounds()) {
+            (None, (lower, upper)) => Ok(Self::new(
                 RangeBound(GreaterOrEqual, lower),
                 upper.map(|ver| {
                     RangeBound(Less, ver.with_pre_release(VersionPreRelease::default()))
                 }),
             )),
+            (Some(Simple(Less | Greater)), _) if is_major => Err(ComparatorBeforeWildcard),
             (Some(Simple(comp)), bounds) => match (comp, bounds) {
-                (LessOrEqual | Equal | GreaterOrEqual, (lower, None)) => {
-                    Some(Self::new(RangeBound(GreaterOrEqual, lower), None))
+                (GreaterOrEqual, (lower, _)) | (Equal, (lower, None)) => {
+                    Ok(Self::new(RangeBound(GreaterOrEqual, lower), None))
                 }
-                (comp @ (Greater | GreaterOrEqual), (lower, Some(upper))) => {
-                    let bound = match comp {
-                        Greater => upper,
-                        _ => lower,
-                    };
-
-                    Some(Self::new(RangeBound(GreaterOrEqual, bound), None))
+                (LessOrEqual, (_, None)) => Ok(Self::new(
+                    RangeBound(GreaterOrEqual, Version::new(0, 0, 0)),
+                    None,
+                )),
+                (Greater, (_, Some(upper))) => {
+                    Ok(Self::new(RangeBound(GreaterOrEqual, upper), None))
                 }
-                (Equal, (lower, Some(upper))) => Some(Self::new(
+                // nothing is greater than the largest representable versions
+                (Greater, (_, None)) => Ok(Self::new(
+                    RangeBound(
+                        Less,
+                        Version::new(0, 0, 0).with_pre_release(VersionPreRelease::default()),
+                    ),
+                    None,
+                )),
+                (Equal, (lower, Some(upper))) => Ok(Self::new(
                     RangeBound(GreaterOrEqual, lower),
                     Some(RangeBound(
                         Less,
                         upper.with_pre_release(VersionPreRelease::default()),
                     )),
                 )),
-                (comp @ (Less | LessOrEqual), (lower, Some(upper))) => {
-                    let bound = match comp {
-                        Less => lower,
-                        _ => upper,
+                (comp @ (Less | LessOrEqual), (lower, upper)) => {
+                    let bound = match (comp, upper) {
+                        (LessOrEqual, Some(upper)) => upper,
+                        _ => lower,
                     };
 
-                    Some(Self::new(
+                    Ok(Self::new(
                         RangeBound(Less, bound.with_pre_release(VersionPreRelease::default())),
                         None,
                     ))
                 }
-                _ => None,
             },
-            (Some(Tilde), (lower, Some(upper))) => Some(Self::new(
+            // `^1.2` only keeps the major version fixed, while `^0.2` keeps the minor one as well, same as `~0.2`
+            (Some(Caret), _) if matches!(pat, VersionPattern::Patch(major, _) if major != 0) => {
+                let (lower, _) = pat.to_bounds();
+                let (_, upper) = VersionPattern::Minor(lower.core.major).to_bounds();
+
+                Ok(Self::new(
+                    RangeBound(GreaterOrEqual, lower),
+                    upper.map(|ver| {
+                        RangeBound(Less, ver.with_pre_release(VersionPreRelease::default()))
+                    }),
+                ))
+            }
+            (Some(Tilde | Caret), (lower, upper)) => Ok(Self::new(
                 RangeBound(GreaterOrEqual, lower),
-                Some(RangeBound(
-                    Less,
-                    upper.with_pre_release(VersionPreRelease::default()),
-                )),
+                upper.map(|ver| {
+                    RangeBound(Less, ver.with_pre_release(VersionPreRelease::default()))
+                }),
             )),
-            _ => None,
-        }
+        };
+
+        unit.map(|unit| unit.with_sugar(sugar))
     }
 
     fn merge_parts(first: ParsedPart, second: ParsedPart) -> Self {
@@ -253,7 +383,16 @@ impl RangeUnit {
 
 #[test]
 fn test_parse() {
+    use crate::version::error::VersionParseErrorKind;
+    use RangeParseErrorKind::*;
+
     let parse = |s| RangeUnit::parse(s).expect(s).0.to_string();
+    let err = |s| {
+        RangeUnit::parse(s)
+            .map(|(u, _)| u.to_string())
+            .unwrap_err()
+            .kind
+    };
 
     // version, no comparator
     assert_eq!("1.2.3-foo", parse("1.2.3-foo+bar"));
@@ -275,13 +414,14 @@ fn test_parse() {
     assert_eq!(">=1.0.0 <2.0.0-0", parse("1"));
     assert_eq!(">=1.2.0 <1.3.0-0", parse("1.2"));
     // major pattern, with comparator
-    assert_eq!(None, RangeUnit::parse(">*"));
+    assert_eq!(ComparatorBeforeWildcard, err(">*"));
     assert_eq!(">=0.0.0", parse(">=*"));
     assert_eq!(">=0.0.0", parse("=*"));
     assert_eq!(">=0.0.0", parse("<=*"));
-    assert_eq!(None, RangeUnit::parse("<*"));
-    assert_eq!(None, RangeUnit::parse("~*"));
-    assert_eq!(None, RangeUnit::parse("^*"));
+    assert_eq!(ComparatorBeforeWildcard, err("<*"));
+    assert_eq!(">=0.0.0", parse("~*"));
+    assert_eq!(">=0.0.0", parse("^*"));
+    assert_eq!(">=0.0.0", parse("^x"));
     // minor pattern, with comparator
     assert_eq!("<1.0.0-0", parse("<1"));
     assert_eq!("<2.0.0-0", parse("<=1"));
@@ -289,7 +429,11 @@ fn test_parse() {
     assert_eq!(">=1.0.0", parse(">=1"));
     assert_eq!(">=2.0.0", parse(">1"));
     assert_eq!(">=1.0.0 <2.0.0-0", parse("~1"));
-    assert_eq!(None, RangeUnit::parse("^1"));
+    assert_eq!(">=1.0.0 <2.0.0-0", parse("~>1"));
+    assert_eq!(">=1.0.0 <2.0.0-0", parse("^1"));
+    assert_eq!(">=1.0.0 <2.0.0-0", parse("^1.x"));
+    assert_eq!(">=0.0.0 <1.0.0-0", parse("^0"));
+    assert_eq!(">=0.0.0 <1.0.0-0", parse("^0.x"));
     // patch pattern, with comparator
     assert_eq!("<1.2.0-0", parse("<1.2"));
     assert_eq!("<1.3.0-0", parse("<=1.2"));
@@ -297,7 +441,20 @@ fn test_parse() {
     assert_eq!(">=1.2.0", parse(">=1.2"));
     assert_eq!(">=1.3.0", parse(">1.2"));
     assert_eq!(">=1.2.0 <1.3.0-0", parse("~1.2"));
-    assert_eq!(None, RangeUnit::parse("^1.2"));
+    assert_eq!(">=1.2.0 <1.3.0-0", parse("~>1.2"));
+    assert_eq!(">=1.2.0 <1.3.0-0", parse("~1.2.x"));
+    assert_eq!(">=1.2.0 <2.0.0-0", parse("^1.2"));
+    assert_eq!(">=1.2.0 <2.0.0-0", parse("^1.2.x"));
+    assert_eq!(">=0.2.0 <0.3.0-0", parse("^0.2"));
+    assert_eq!(">=0.0.0 <0.1.0-0", parse("^0.0"));
+    assert_eq!(">=0.0.0 <0.1.0-0", parse("^0.0.x"));
+    // full version, tilde with `>`
+    assert_eq!(">=1.2.3 <1.3.0-0", parse("~>1.2.3"));
+    // pattern with a pre-release tag
+    assert_eq!(PreReleaseOnPattern, err("1.2.x-beta"));
+    assert_eq!(PreReleaseOnPattern, err("^1.2-beta"));
+    assert_eq!(PreReleaseOnPattern, err("*-0"));
+    assert_eq!(PreReleaseOnPattern, err("1.2.3 - 2.x-rc"));
     // hypen range
     assert_eq!(">=1.2.3 <=4.5.6", parse("1.2.3 - 4.5.6"));
     assert_eq!(">=1.2.3 <4.6.0-0", parse("1.2.3 - 4.5"));
@@ -307,5 +464,56 @@ fn test_parse() {
     assert_eq!(">=1.2.0 <3.5.0-0", parse("1.2 - 3.4"));
     assert_eq!(">=1.0.0 <=3.4.5", parse("1 - 3.4.5"));
     assert_eq!(">=0.0.0 <=3.4.5", parse("* - 3.4.5"));
-    assert_eq!(None, RangeUnit::parse(">1 - 2"))
+    assert_eq!(ComparatorBeforeHyphen, err(">1 - 2"));
+    // overflowing upper bounds
+    assert_eq!(">=18446744073709551615.0.0", parse("~18446744073709551615"));
+    assert_eq!(
+        ">=18446744073709551615.2.0",
+        parse("^18446744073709551615.2")
+    );
+    assert_eq!(
+        ">=18446744073709551615.2.3",
+        parse("^18446744073709551615.2.3")
+    );
+    assert_eq!(
+        ">=1.18446744073709551615.3 <2.0.0-0",
+        parse("~1.18446744073709551615.3")
+    );
+    assert_eq!(
+        ">=0.0.18446744073709551615 <0.1.0-0",
+        parse("^0.0.18446744073709551615")
+    );
+    assert_eq!(
+        ">=18446744073709551615.0.0",
+        parse("18446744073709551615.x")
+    );
+    assert_eq!(
+        ">=1.18446744073709551615.0 <2.0.0-0",
+        parse("1.18446744073709551615")
+    );
+    assert_eq!(
+        "<18446744073709551615.0.0-0",
+        parse("<18446744073709551615")
+    );
+    assert_eq!(">=0.0.0", parse("<=18446744073709551615"));
+    assert_eq!("<0.0.0-0", parse(">18446744073709551615"));
+    assert_eq!(">=18446744073709551615.0.0", parse("=18446744073709551615"));
+    assert_eq!(">=1.2.3", parse("1.2.3 - 18446744073709551615.x"));
+    // malformed versions
+    assert_eq!(
+        Err(UnitParseError {
+            kind: InvalidUnit,
+            rest: "03",
+            cause: Some(VersionParseErrorKind::LeadingZero)
+        }),
+        RangeUnit::parse("<1.2.03").map(|(u, _)| u.to_string())
+    );
+    assert_eq!(
+        Err(UnitParseError {
+            kind: InvalidUnit,
+            rest: ".bar",
+            cause: Some(VersionParseErrorKind::EmptyIdentifier)
+        }),
+        RangeUnit::parse("1.2.3 - 4.5.6-foo..bar").map(|(u, _)| u.to_string())
+    );
 }
@@ -1,9 +1,37 @@
 use std::fmt;
 
-use super::common::parse_dot_sep_list;
+use super::common::{parse_dot_sep_list, parse_whole, ParseResult};
+use super::error::VersionParseError;
 
 #[derive(Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
-pub struct VersionBuild(pub String);
+pub struct VersionBuild(pub(crate) String);
+
+impl VersionBuild {
+    /// Builds build metadata out of dot-separated identifiers (e.g. `sha.5114f85`, without the leading plus sign),
+    /// validating them the same way `Version::from` does.
+    pub fn new(s: &str) -> Result<Self, VersionParseError> {
+        parse_whole(s, |s| parse_dot_sep_list(s, |_| Ok(()))).map(Self)
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+#[test]
+fn test_new() {
+    use super::error::VersionParseErrorKind::*;
+
+    let err = |s| VersionBuild::new(s).map_err(|e| (e.kind(), e.offset()));
+
+    assert_eq!(
+        Ok("001.a"),
+        VersionBuild::new("001.a").as_ref().map(|b| b.as_str())
+    );
+    assert_eq!(Err((EmptyIdentifier, 0)), err(""));
+    assert_eq!(Err((InvalidCharacter, 2)), err("a.💩"));
+    assert_eq!(Err((TrailingInput, 1)), err("a+b"));
+}
 
 impl fmt::Display for VersionBuild {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
@@ -17,22 +45,24 @@ fn test_to_string() {
 }
 
 impl VersionBuild {
-    pub(crate) fn parse(s: &str) -> Option<(Option<Self>, &str)> {
+    pub(crate) fn parse(s: &str) -> ParseResult<'_, Option<Self>> {
         if let Some(r) = s.strip_prefix('+') {
-            parse_dot_sep_list(r, |_| true).map(|(s, r)| (Some(Self(s)), r))
+            parse_dot_sep_list(r, |_| Ok(())).map(|(s, r)| (Some(Self(s)), r))
         } else {
-            Some((None, s))
+            Ok((None, s))
         }
     }
 }
 
 #[test]
 fn test_parse() {
-    assert_eq!(Some((None, "foo")), VersionBuild::parse("foo"));
-    assert_eq!(Some((None, "-foo")), VersionBuild::parse("-foo"));
-    assert_eq!(None, VersionBuild::parse("+"));
+    use super::error::VersionParseErrorKind::*;
+
+    assert_eq!(Ok((None, "foo")), VersionBuild::parse("foo"));
+    assert_eq!(Ok((None, "-foo")), VersionBuild::parse("-foo"));
+    assert_eq!(Err((EmptyIdentifier, "")), VersionBuild::parse("+"));
     assert_eq!(
-        Some((Some(VersionBuild("foo".to_string())), "")),
+        Ok((Some(VersionBuild("foo".to_string())), "")),
         VersionBuild::parse("+foo")
     );
 }
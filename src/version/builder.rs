@@ -0,0 +1,89 @@
+use super::build::VersionBuild;
+use super::error::VersionParseError;
+use super::pre_release::VersionPreRelease;
+use super::Version;
+
+/// Assembles a `Version` piece by piece, validating the pre-release tag and build metadata on the way.
+///
+/// The first validation failure is kept and returned by `finish`.
+#[derive(Clone, Debug)]
+pub struct VersionBuilder {
+    version: Result<Version, VersionParseError>,
+}
+
+impl VersionBuilder {
+    pub fn new(major: u64, minor: u64, patch: u64) -> Self {
+        Self {
+            version: Ok(Version::new(major, minor, patch)),
+        }
+    }
+
+    /// Sets the pre-release tag, given as dot-separated identifiers without the leading hyphen (e.g. `rc.1`).
+    pub fn pre_release(mut self, s: &str) -> Self {
+        if let Ok(ver) = &mut self.version {
+            match VersionPreRelease::new(s) {
+                Ok(pre_release) => ver.pre_release = Some(pre_release),
+                Err(err) => self.version = Err(err),
+            }
+        }
+
+        self
+    }
+
+    /// Sets the build metadata, given as dot-separated identifiers without the leading plus sign (e.g. `sha.5114f85`).
+    pub fn build(mut self, s: &str) -> Self {
+        if let Ok(ver) = &mut self.version {
+            match VersionBuild::new(s) {
+                Ok(build) => ver.build = Some(build),
+                Err(err) => self.version = Err(err),
+            }
+        }
+
+        self
+    }
+
+    pub fn finish(self) -> Result<Version, VersionParseError> {
+        self.version
+    }
+}
+
+impl Version {
+    /// Shorthand for `VersionBuilder::new`.
+    pub fn builder(major: u64, minor: u64, patch: u64) -> VersionBuilder {
+        VersionBuilder::new(major, minor, patch)
+    }
+}
+
+#[test]
+fn test_finish() {
+    use super::error::VersionParseErrorKind::*;
+
+    let finish = |b: VersionBuilder| b.finish().map(|v| v.to_string());
+    let err = |b: VersionBuilder| b.finish().map_err(|e| (e.kind(), e.input().to_string()));
+
+    assert_eq!(Ok("1.2.3".to_string()), finish(Version::builder(1, 2, 3)));
+    assert_eq!(
+        Ok("1.2.3-rc.1+sha.0a".to_string()),
+        finish(
+            Version::builder(1, 2, 3)
+                .pre_release("rc.1")
+                .build("sha.0a")
+        )
+    );
+    assert_eq!(
+        Ok("1.2.3-beta".to_string()),
+        finish(
+            Version::builder(1, 2, 3)
+                .pre_release("alpha")
+                .pre_release("beta")
+        )
+    );
+    assert_eq!(
+        Err((LeadingZero, "rc.01".to_string())),
+        err(Version::builder(1, 2, 3).pre_release("rc.01").build("foo"))
+    );
+    assert_eq!(
+        Err((EmptyIdentifier, "".to_string())),
+        err(Version::builder(1, 2, 3).build("").pre_release("foo..bar"))
+    );
+}
@@ -0,0 +1,209 @@
+use super::build::VersionBuild;
+use super::common::parse_num_id;
+use super::core::VersionCore;
+use super::pre_release::VersionPreRelease;
+use super::Version;
+
+/// Longest numeric component `node-semver` will pick up when coercing.
+const MAX_COMPONENT_LEN: usize = 16;
+
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct CoerceOptions {
+    /// Look for the rightmost version-like sequence instead of the leftmost one.
+    pub rtl: bool,
+    /// Keep the pre-release tag and build metadata that immediately follow the version core.
+    pub include_prerelease: bool,
+}
+
+struct CoerceMatch<'a> {
+    parts: [&'a str; 3],
+    pre_release: Option<VersionPreRelease>,
+    build: Option<VersionBuild>,
+    rest: &'a str,
+}
+
+impl CoerceMatch<'_> {
+    fn find(s: &str, include_prerelease: bool) -> Option<CoerceMatch<'_>> {
+        let mut parts = [""; 3];
+        let mut r = s;
+
+        for (idx, part) in parts.iter_mut().enumerate() {
+            let t = match (idx, r.strip_prefix('.')) {
+                (0, _) => r,
+                (_, Some(t)) => t,
+                (_, None) => break,
+            };
+
+            let cnt = t.chars().take_while(char::is_ascii_digit).count();
+
+            if cnt == 0 || cnt > MAX_COMPONENT_LEN {
+                if idx == 0 {
+                    return None;
+                }
+
+                break;
+            }
+
+            *part = &t[..cnt];
+            r = &t[cnt..];
+        }
+
+        let mut pre_release = None;
+        let mut build = None;
+
+        if include_prerelease {
+            if let Some((pre, t)) = Self::parse_pre_release(r) {
+                pre_release = pre;
+                r = t;
+            }
+
+            if let Ok((b, t)) = VersionBuild::parse(r) {
+                build = b;
+                r = t;
+            }
+        }
+
+        Some(CoerceMatch {
+            parts,
+            pre_release,
+            build,
+            rest: r,
+        })
+    }
+
+    /// Parses the longest valid prefix of the pre-release tag, so an invalid identifier drops only itself
+    /// and those after it (e.g. `-rc.01` yields `rc`).
+    fn parse_pre_release(s: &str) -> Option<(Option<VersionPreRelease>, &str)> {
+        let mut end = s.len();
+
+        loop {
+            if let Ok((pre, t)) = VersionPreRelease::parse(&s[..end]) {
+                return Some((pre, &s[(end - t.len())..]));
+            }
+
+            end = s[..end].rfind('.')?;
+        }
+    }
+
+    fn into_version(self) -> Option<Version> {
+        let mut nums = [0; 3];
+
+        for (num, part) in nums.iter_mut().zip(self.parts) {
+            if !part.is_empty() {
+                *num = parse_num_id(part).ok()?.0;
+            }
+        }
+
+        Some(Version {
+            core: VersionCore::new(nums[0], nums[1], nums[2]),
+            pre_release: self.pre_release,
+            build: self.build,
+        })
+    }
+}
+
+impl Version {
+    /// Extracts a version from an arbitrary string (e.g. `"release-1.4 final"`) following `node-semver`'s `coerce`.
+    ///
+    /// The leftmost sequence of one to three dot-separated numbers is picked up, with the missing parts set to `0`.
+    /// If `opts.rtl` is set, then the rightmost such sequence is preferred instead (so `"1.2.3.4"` yields `2.3.4`).
+    ///
+    /// If `opts.include_prerelease` is set, then the pre-release tag and build metadata following the numbers are kept,
+    /// with the pre-release tag cut short before its first invalid identifier (so `"1.2.3-rc.01"` yields `1.2.3-rc`).
+    /// Otherwise, they're always dropped.
+    ///
+    /// Returns `None` if there are no numbers to pick up, or if the chosen ones aren't valid (e.g. have leading zeros).
+    pub fn coerce(s: &str, opts: CoerceOptions) -> Option<Self> {
+        let bytes = s.as_bytes();
+        let starts = (0..s.len()).filter(|&idx| {
+            bytes[idx].is_ascii_digit() && (idx == 0 || !bytes[idx - 1].is_ascii_digit())
+        });
+
+        let mut found = None::<CoerceMatch>;
+
+        for start in starts {
+            let Some(next) = CoerceMatch::find(&s[start..], opts.include_prerelease) else {
+                continue;
+            };
+
+            if !opts.rtl {
+                found = Some(next);
+                break;
+            }
+
+            // a shorter match ending at the same position as the previous one isn't preferred
+            if found
+                .as_ref()
+                .is_none_or(|m| m.rest.len() != next.rest.len())
+            {
+                found = Some(next);
+            }
+
+            if found.as_ref().is_some_and(|m| m.rest.is_empty()) {
+                break;
+            }
+        }
+
+        found?.into_version()
+    }
+}
+
+#[test]
+fn test_coerce() {
+    let test = |s, rtl, include_prerelease| {
+        let opts = CoerceOptions {
+            rtl,
+            include_prerelease,
+        };
+
+        Version::coerce(s, opts).map(|v| v.to_string())
+    };
+    let ltr = |s| test(s, false, false);
+    let rtl = |s| test(s, true, false);
+    let full = |s| test(s, false, true);
+
+    assert_eq!(None, ltr(""));
+    assert_eq!(None, ltr("foo"));
+    assert_eq!(Some("1.4.0".to_string()), ltr("release-1.4 final"));
+    assert_eq!(Some("3.11.0".to_string()), ltr("Python 3.11"));
+    assert_eq!(Some("2.0.0".to_string()), ltr("v2"));
+    assert_eq!(Some("10.0.19045".to_string()), ltr("build 10.0.19045.3803"));
+    assert_eq!(Some("1.0.0".to_string()), ltr(".1."));
+    assert_eq!(Some("1.0.0".to_string()), ltr("..1"));
+    assert_eq!(Some("1.2.3".to_string()), ltr("1.2.3-rc.1+foo"));
+    assert_eq!(Some("3.4.0".to_string()), ltr("v3.4 replaces v3.3.1"));
+    assert_eq!(Some("4.6.3".to_string()), ltr("4.6.3.9.2-alpha2"));
+    assert_eq!(Some("1.0.0".to_string()), ltr("1.12345678901234567"));
+    assert_eq!(None, ltr("12345678901234567"));
+    assert_eq!(None, ltr("1.02"));
+    assert_eq!(Some("4294967296.0.0".to_string()), ltr("4294967296"));
+    assert_eq!(
+        Some("1111111111111111.0.0".to_string()),
+        ltr("a1111111111111111")
+    );
+
+    assert_eq!(Some("2.3.4".to_string()), rtl("1.2.3.4"));
+    assert_eq!(Some("4.5.6".to_string()), rtl("1.2.3.4.5.6"));
+    assert_eq!(Some("2.3.4".to_string()), rtl("10.2.3.4"));
+    assert_eq!(Some("4.0.0".to_string()), rtl("1.2.3/4"));
+    assert_eq!(Some("1.2.3".to_string()), rtl("1.2.3/"));
+    assert_eq!(Some("3.3.1".to_string()), rtl("v3.4 replaces v3.3.1"));
+
+    assert_eq!(
+        Some("1.2.3-rc.1+rev.2".to_string()),
+        full("1.2.3-rc.1+rev.2")
+    );
+    assert_eq!(Some("1.2.0-rc.1".to_string()), full("v1.2-rc.1 final"));
+    assert_eq!(Some("1.2.3+build".to_string()), full("1.2.3+build"));
+    assert_eq!(Some("1.2.3".to_string()), full("1.2.3.4-rc.1"));
+    assert_eq!(Some("1.2.3-rc".to_string()), full("1.2.3-rc.01"));
+    assert_eq!(
+        Some("1.2.3-rc.1".to_string()),
+        full("1.2.3-rc.1.02.beta+build")
+    );
+    assert_eq!(Some("1.2.3".to_string()), full("1.2.3-01"));
+    assert_eq!(
+        Some("2.0.0-beta".to_string()),
+        test("1.0.0 -> 2.0.0-beta", true, true)
+    );
+}
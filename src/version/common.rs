@@ -1,23 +1,89 @@
-pub(crate) fn parse_num_id(s: &str) -> Option<(u32, &str)> {
+use super::error::{VersionParseError, VersionParseErrorKind};
+
+/// On failure, carries the remainder of the input starting at the offending position.
+pub(crate) type ParseResult<'a, T> = Result<(T, &'a str), (VersionParseErrorKind, &'a str)>;
+
+pub(crate) fn parse_num_id(s: &str) -> ParseResult<'_, u64> {
+    use VersionParseErrorKind::*;
+
     let cnt = s.chars().take_while(|c| c.is_ascii_digit()).count();
 
-    if cnt == 0 || (cnt > 1 && s.starts_with('0')) {
-        return None;
+    if cnt == 0 {
+        let kind = if s.is_empty() || s.starts_with('.') {
+            MissingComponent
+        } else {
+            InvalidCharacter
+        };
+
+        return Err((kind, s));
+    }
+
+    if cnt > 1 && s.starts_with('0') {
+        return Err((LeadingZero, s));
     }
 
-    s[..cnt].parse().ok().map(|id| (id, &s[cnt..]))
+    s[..cnt]
+        .parse()
+        .map(|id| (id, &s[cnt..]))
+        .map_err(|_| (NumericOverflow, s))
 }
 
 #[test]
 fn test_parse_num_id() {
-    assert_eq!(None, parse_num_id(""));
-    assert_eq!(None, parse_num_id("a1"));
-    assert_eq!(None, parse_num_id("01"));
-    assert_eq!(Some((0, "")), parse_num_id("0"));
-    assert_eq!(Some((102, ".")), parse_num_id("102."));
+    use VersionParseErrorKind::*;
+
+    assert_eq!(Err((MissingComponent, "")), parse_num_id(""));
+    assert_eq!(Err((MissingComponent, ".1")), parse_num_id(".1"));
+    assert_eq!(Err((InvalidCharacter, "a1")), parse_num_id("a1"));
+    assert_eq!(Err((LeadingZero, "01")), parse_num_id("01"));
+    assert_eq!(
+        Err((NumericOverflow, "18446744073709551616")),
+        parse_num_id("18446744073709551616")
+    );
+    assert_eq!(
+        Ok((18446744073709551615, "")),
+        parse_num_id("18446744073709551615")
+    );
+    assert_eq!(Ok((0, "")), parse_num_id("0"));
+    assert_eq!(Ok((102, ".")), parse_num_id("102."));
+}
+
+/// Same as `parse_num_id`, but tolerates leading zeros.
+pub(crate) fn parse_num_id_loose(s: &str) -> ParseResult<'_, u64> {
+    let zeros = s.chars().take_while(|c| *c == '0').count();
+    let t = &s[zeros..];
+
+    // keep the last zero if there's nothing else
+    let t = if zeros == 0 || t.starts_with(|c: char| c.is_ascii_digit()) {
+        t
+    } else {
+        &s[(zeros - 1)..]
+    };
+
+    parse_num_id(t).map_err(|(kind, _)| (kind, s))
+}
+
+#[test]
+fn test_parse_num_id_loose() {
+    use VersionParseErrorKind::*;
+
+    assert_eq!(Err((MissingComponent, "")), parse_num_id_loose(""));
+    assert_eq!(Err((InvalidCharacter, "a1")), parse_num_id_loose("a1"));
+    assert_eq!(Ok((1, "")), parse_num_id_loose("01"));
+    assert_eq!(Ok((0, ".")), parse_num_id_loose("000."));
+    assert_eq!(Ok((102, "a")), parse_num_id_loose("00102a"));
+    assert_eq!(
+        Err((NumericOverflow, "018446744073709551616")),
+        parse_num_id_loose("018446744073709551616")
+    );
 }
 
-pub(crate) fn parse_dot_sep_list(s: &str, check_part: fn(&str) -> bool) -> Option<(String, &str)> {
+pub(crate) fn parse_dot_sep_list(
+    s: &str,
+    check_part: fn(&str) -> Result<(), VersionParseErrorKind>,
+) -> ParseResult<'_, String> {
+    use VersionParseErrorKind::*;
+
     let mut r = s;
 
     loop {
@@ -26,10 +92,17 @@ pub(crate) fn parse_dot_sep_list(s: &str, check_part: fn(&str) -> bool) -> Optio
             .take_while(|c| *c == '-' || c.is_ascii_alphanumeric())
             .count();
 
-        if cnt == 0 || !check_part(&r[..cnt]) {
-            return None;
+        if cnt == 0 {
+            let kind = match r.chars().next() {
+                None | Some('.' | '+') => EmptyIdentifier,
+                _ => InvalidCharacter,
+            };
+
+            return Err((kind, r));
         }
 
+        check_part(&r[..cnt]).map_err(|kind| (kind, r))?;
+
         r = &r[cnt..];
 
         if let Some(t) = r.strip_prefix('.') {
@@ -39,31 +112,67 @@ pub(crate) fn parse_dot_sep_list(s: &str, check_part: fn(&str) -> bool) -> Optio
         }
     }
 
-    if s != r {
-        Some((String::from(&s[..(s.len() - r.len())]), r))
-    } else {
-        None
-    }
+    Ok((String::from(&s[..(s.len() - r.len())]), r))
 }
 
 #[test]
 fn test_parse_dot_sep_list() {
-    assert_eq!(None, parse_dot_sep_list("", |_| true));
-    assert_eq!(None, parse_dot_sep_list(".foo", |_| true));
-    assert_eq!(None, parse_dot_sep_list("foo.", |_| true));
-    assert_eq!(None, parse_dot_sep_list("foo..bar", |_| true));
-    assert_eq!(None, parse_dot_sep_list("foo.bar", |p| p != "bar"));
-    assert_eq!(None, parse_dot_sep_list("foo.💩.bar", |_| true));
+    use VersionParseErrorKind::*;
+
+    assert_eq!(
+        Err((EmptyIdentifier, "")),
+        parse_dot_sep_list("", |_| Ok(()))
+    );
+    assert_eq!(
+        Err((EmptyIdentifier, ".foo")),
+        parse_dot_sep_list(".foo", |_| Ok(()))
+    );
+    assert_eq!(
+        Err((EmptyIdentifier, "")),
+        parse_dot_sep_list("foo.", |_| Ok(()))
+    );
+    assert_eq!(
+        Err((EmptyIdentifier, ".bar")),
+        parse_dot_sep_list("foo..bar", |_| Ok(()))
+    );
+    assert_eq!(
+        Err((LeadingZero, "bar")),
+        parse_dot_sep_list("foo.bar", |p| if p != "bar" {
+            Ok(())
+        } else {
+            Err(LeadingZero)
+        })
+    );
     assert_eq!(
-        Some(("foo.01".to_string(), "")),
-        parse_dot_sep_list("foo.01", |_| true)
+        Err((InvalidCharacter, "💩.bar")),
+        parse_dot_sep_list("foo.💩.bar", |_| Ok(()))
     );
     assert_eq!(
-        Some(("a".to_string(), "💩.b")),
-        parse_dot_sep_list("a💩.b", |_| true)
+        Ok(("foo.01".to_string(), "")),
+        parse_dot_sep_list("foo.01", |_| Ok(()))
     );
     assert_eq!(
-        Some(("-Ab1".to_string(), "_")),
-        parse_dot_sep_list("-Ab1_", |_| true)
+        Ok(("a".to_string(), "💩.b")),
+        parse_dot_sep_list("a💩.b", |_| Ok(()))
     );
+    assert_eq!(
+        Ok(("-Ab1".to_string(), "_")),
+        parse_dot_sep_list("-Ab1_", |_| Ok(()))
+    );
+}
+
+/// Runs `parse` over the whole of `s`, reporting any leftover input as an error.
+pub(crate) fn parse_whole<'a, T>(
+    s: &'a str,
+    parse: impl FnOnce(&'a str) -> ParseResult<'a, T>,
+) -> Result<T, VersionParseError> {
+    match parse(s) {
+        Ok((out, "")) => Ok(out),
+        Ok((_, r)) => Err(VersionParseError::new(
+            s,
+            r,
+            VersionParseErrorKind::TrailingInput,
+        )),
+        Err((kind, r)) => Err(VersionParseError::new(s, r, kind)),
+    }
 }
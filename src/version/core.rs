@@ -1,16 +1,17 @@
 use std::{cmp, fmt};
 
-use super::common::parse_num_id;
+use super::common::{parse_num_id, parse_num_id_loose, ParseResult};
+use super::error::VersionParseErrorKind;
 
 #[derive(Clone, Debug, Eq, Hash, PartialEq)]
 pub struct VersionCore {
-    pub major: u32,
-    pub minor: u32,
-    pub patch: u32,
+    pub major: u64,
+    pub minor: u64,
+    pub patch: u64,
 }
 
 impl VersionCore {
-    pub(crate) fn new(major: u32, minor: u32, patch: u32) -> Self {
+    pub fn new(major: u64, minor: u64, patch: u64) -> Self {
         Self {
             major,
             minor,
@@ -62,35 +63,86 @@ fn test_cmp() {
 }
 
 impl VersionCore {
-    pub(crate) fn parse(s: &str) -> Option<(Self, &str)> {
+    pub(crate) fn parse(s: &str) -> ParseResult<'_, Self> {
+        Self::parse_parts(s, parse_num_id)
+    }
+
+    pub(crate) fn parse_loose(s: &str) -> ParseResult<'_, Self> {
+        Self::parse_parts(s, parse_num_id_loose)
+    }
+
+    fn parse_parts(s: &str, parse_part: fn(&str) -> ParseResult<'_, u64>) -> ParseResult<'_, Self> {
         let mut r = s;
-        let mut parts = [0_u32; 3];
+        let mut parts = [0_u64; 3];
 
         for (idx, part) in parts.iter_mut().enumerate() {
             if idx != 0 {
-                r = r.strip_prefix('.')?;
+                r = r.strip_prefix('.').ok_or_else(|| {
+                    let kind = if r.starts_with(|c: char| c.is_ascii_alphanumeric()) {
+                        VersionParseErrorKind::InvalidCharacter
+                    } else {
+                        VersionParseErrorKind::MissingComponent
+                    };
+
+                    (kind, r)
+                })?;
             }
 
-            (*part, r) = parse_num_id(r)?;
+            (*part, r) = parse_part(r)?;
         }
 
-        Some((Self::new(parts[0], parts[1], parts[2]), r))
+        Ok((Self::new(parts[0], parts[1], parts[2]), r))
     }
 }
 
 #[test]
 fn test_parse() {
-    assert_eq!(None, VersionCore::parse("1"));
-    assert_eq!(None, VersionCore::parse("1."));
-    assert_eq!(None, VersionCore::parse("1.2"));
-    assert_eq!(None, VersionCore::parse("1.2."));
-    assert_eq!(None, VersionCore::parse(".1.2.3"));
-    assert_eq!(None, VersionCore::parse("1..2"));
-    assert_eq!(None, VersionCore::parse("1.2.03"));
-    assert_eq!(None, VersionCore::parse("1.2.f"));
-    assert_eq!(None, VersionCore::parse("1.2.-3"));
+    use VersionParseErrorKind::*;
+
+    assert_eq!(Err((MissingComponent, "")), VersionCore::parse("1"));
+    assert_eq!(Err((MissingComponent, "")), VersionCore::parse("1."));
+    assert_eq!(Err((MissingComponent, "")), VersionCore::parse("1.2"));
+    assert_eq!(
+        Err((MissingComponent, "-foo")),
+        VersionCore::parse("1.2-foo")
+    );
+    assert_eq!(Err((MissingComponent, "")), VersionCore::parse("1.2."));
+    assert_eq!(
+        Err((MissingComponent, ".1.2.3")),
+        VersionCore::parse(".1.2.3")
+    );
+    assert_eq!(Err((MissingComponent, ".2")), VersionCore::parse("1..2"));
+    assert_eq!(Err((LeadingZero, "03")), VersionCore::parse("1.2.03"));
+    assert_eq!(Err((InvalidCharacter, "f")), VersionCore::parse("1.2.f"));
+    assert_eq!(
+        Err((InvalidCharacter, "x.2.3")),
+        VersionCore::parse("1x.2.3")
+    );
+    assert_eq!(Err((InvalidCharacter, "-3")), VersionCore::parse("1.2.-3"));
     assert_eq!(
-        Some((VersionCore::new(1, 20, 3), ".")),
+        Err((NumericOverflow, "18446744073709551616.0.0")),
+        VersionCore::parse("18446744073709551616.0.0")
+    );
+    assert_eq!(
+        Ok((VersionCore::new(4294967296, 0, 18446744073709551615), "")),
+        VersionCore::parse("4294967296.0.18446744073709551615")
+    );
+    assert_eq!(
+        Ok((VersionCore::new(1, 20, 3), ".")),
         VersionCore::parse("1.20.3.")
     );
 }
+
+#[test]
+fn test_parse_loose() {
+    use VersionParseErrorKind::*;
+
+    assert_eq!(
+        Err((MissingComponent, "")),
+        VersionCore::parse_loose("01.02")
+    );
+    assert_eq!(
+        Ok((VersionCore::new(1, 2, 0), "")),
+        VersionCore::parse_loose("01.002.0")
+    );
+}
@@ -1,4 +1,5 @@
 use super::core::VersionCore;
+use super::error::VersionIncrementError;
 use super::pre_release::VersionPreRelease;
 use super::Version;
 
@@ -11,13 +12,34 @@ pub enum VersionDiff {
     Patch,
     PrePatch,
     PreRelease,
+    /// Only drops the pre-release tag, so it's never returned by `Version::find_difference`.
+    Release,
+}
+
+/// Number that starts a new pre-release tag (or gets appended to a tag without numeric parts),
+/// mirroring `node-semver`'s `identifierBase`.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum IdentifierBase {
+    #[default]
+    Zero,
+    One,
+    /// Don't add a number at all, e.g. `1.2.3` incremented with `PreMinor` and identifier `beta` becomes `1.3.0-beta`.
+    Omitted,
+}
+
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct IncrementOptions {
+    /// Identifier to start the pre-release tag with (e.g. `beta` to get `1.3.0-beta.0` out of `1.2.3` and `PreMinor`).
+    /// It's ignored unless `diff` is `PreMajor | PreMinor | PrePatch | PreRelease`.
+    pub identifier: Option<VersionPreRelease>,
+    pub identifier_base: IdentifierBase,
 }
 
 impl Version {
     /// Increments the version core following the rules described on https://semver.org and depending on `diff`.
     ///
     /// If `diff` is `Major | Minor | Patch` and the version has a pre-release tag, then `pre_release` will
-    /// be dropped but the core will remain unchanged.
+    /// be dropped but the core will remain unchanged. The same happens for `Release`, which requires the tag.
     ///
     /// If `diff` is `PreMajor | PreMinor | PrePatch`, then `pre_release` will always be just set to `0`.
     ///
@@ -27,31 +49,99 @@ impl Version {
     /// If the tag has no numeric parts, then a new `0` part will be appended to it.
     ///
     /// The version's `build` will always be dropped.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the incremented component is already `u64::MAX`, or if `diff` is `Release` and the version doesn't
+    /// have a pre-release tag, see `try_to_incremented` for a checked variant.
     pub fn to_incremented(&self, diff: VersionDiff) -> Self {
+        self.try_to_incremented(diff)
+            .expect("version can't be incremented")
+    }
+
+    /// Same as `to_incremented`, but returns an error instead of panicking if the incremented component
+    /// (or the rightmost numeric part of the pre-release tag) is already `u64::MAX`, or if there's no pre-release tag
+    /// for `Release` to drop.
+    pub fn try_to_incremented(&self, diff: VersionDiff) -> Result<Self, VersionIncrementError> {
+        let mut ver = self.clone();
+        ver.increment(diff, &IncrementOptions::default())?;
+
+        Ok(ver)
+    }
+
+    /// Increments the version in place like `to_incremented` does, following `node-semver`'s `inc` when it comes
+    /// to `opts`: the pre-release tag starts with `opts.identifier` (if any) followed by `opts.identifier_base`.
+    ///
+    /// So `1.2.3` incremented with `PreMinor` and identifier `beta` becomes `1.3.0-beta.0`.
+    /// If the version already has a tag and `diff` is `PreRelease`, then the tag is only incremented if it continues
+    /// the identifier with a number (`1.2.4-beta.3` becomes `1.2.4-beta.4`), otherwise it's replaced
+    /// (`1.2.4-alpha.3` becomes `1.2.4-beta.0`).
+    ///
+    /// The version is left unchanged if an error is returned.
+    pub fn increment(
+        &mut self,
+        diff: VersionDiff,
+        opts: &IncrementOptions,
+    ) -> Result<(), VersionIncrementError> {
         use VersionDiff::*;
 
+        let inc = |num: u64| num.checked_add(1).ok_or(VersionIncrementError::Overflow);
+        let VersionCore {
+            major,
+            minor,
+            patch,
+        } = self.core;
+
+        if diff == Release && self.pre_release.is_none() {
+            return Err(VersionIncrementError::MissingPreRelease);
+        }
+
         let core = match (diff, self.pre_release.is_some()) {
-            (Major | Minor | Patch, true) | (PreRelease, true) => self.core.clone(),
-            (Major | PreMajor, _) => VersionCore::new(self.core.major + 1, 0, 0),
-            (Minor | PreMinor, _) => VersionCore::new(self.core.major, self.core.minor + 1, 0),
+            (Major | Minor | Patch, true) | (PreRelease, true) | (Release, _) => self.core.clone(),
+            (Major | PreMajor, _) => VersionCore::new(inc(major)?, 0, 0),
+            (Minor | PreMinor, _) => VersionCore::new(major, inc(minor)?, 0),
             (Patch | PrePatch, _) | (PreRelease, false) => {
-                VersionCore::new(self.core.major, self.core.minor, self.core.patch + 1)
+                VersionCore::new(major, minor, inc(patch)?)
             }
         };
 
-        let pre_release = match (diff, &self.pre_release) {
-            (Major | Minor | Patch, _) => None,
-            (PreMajor | PreMinor | PrePatch, _) | (PreRelease, None) => {
-                Some(VersionPreRelease::default())
+        let pre_release = match diff {
+            Major | Minor | Patch | Release => None,
+            PreMajor | PreMinor | PrePatch | PreRelease => {
+                let pre = match diff {
+                    PreRelease => self.pre_release.as_ref(),
+                    _ => None,
+                };
+
+                Some(VersionPreRelease::try_to_incremented(
+                    pre,
+                    opts.identifier.as_ref(),
+                    opts.identifier_base,
+                )?)
             }
-            (PreRelease, Some(pre)) => Some(pre.to_incremented()),
         };
 
-        Self {
+        *self = Self {
             core,
             pre_release,
             build: None,
-        }
+        };
+
+        Ok(())
+    }
+
+    /// Finds the exclusive upper bound for versions that only differ from this one below `diff`, which must be
+    /// one of `PreMajor | PreMinor | PrePatch` (e.g. `1.3.0-0` for `1.2.3` and `PreMinor`).
+    ///
+    /// If the component can't be incremented, then the next larger one is used instead (so `1.2.<u64::MAX>` and
+    /// `PrePatch` yield `1.3.0-0`). Returns `None` if there's no such bound at all.
+    pub(crate) fn to_upper_bound(&self, diff: VersionDiff) -> Option<Self> {
+        use VersionDiff::*;
+
+        [PrePatch, PreMinor, PreMajor]
+            .into_iter()
+            .skip_while(|&d| d != diff)
+            .find_map(|d| self.try_to_incremented(d).ok())
     }
 }
 
@@ -77,6 +167,156 @@ fn test_to_incremented() {
     assert_eq!("1.2.4-0", test("1.2.3", PreRelease));
     assert_eq!("1.2.3-foo.0", test("1.2.3-foo", PreRelease));
     assert_eq!("1.2.3-0.foo.1.bar", test("1.2.3-0.foo.0.bar", PreRelease));
+    assert_eq!("1.2.3", test("1.2.3-foo", Release));
+    assert_eq!("1.2.3", test("1.2.3-foo+bar", Release));
+}
+
+#[test]
+fn test_try_to_incremented() {
+    use VersionDiff::*;
+
+    let max = u64::MAX;
+    let test = |v: Version, d| v.try_to_incremented(d).map(|v| v.to_string());
+
+    assert_eq!(
+        Err(VersionIncrementError::Overflow),
+        test(Version::new(max, 0, 0), Major)
+    );
+    assert_eq!(
+        Err(VersionIncrementError::Overflow),
+        test(Version::new(1, max, 0), PreMinor)
+    );
+    assert_eq!(
+        Err(VersionIncrementError::Overflow),
+        test(Version::new(1, 2, max), PreRelease)
+    );
+    assert_eq!(
+        Ok(format!("{}.0.0", max)),
+        test(Version::new(max - 1, 5, 5), Major)
+    );
+    assert_eq!(
+        Ok(format!("{}.0.0", max)),
+        test(
+            Version::builder(max, 0, 0)
+                .pre_release("rc")
+                .finish()
+                .unwrap(),
+            Major
+        )
+    );
+    assert_eq!(
+        Err(VersionIncrementError::Overflow),
+        test(
+            Version::builder(1, 2, 3)
+                .pre_release("rc.18446744073709551615")
+                .finish()
+                .unwrap(),
+            PreRelease
+        )
+    );
+    assert_eq!(
+        Err(VersionIncrementError::MissingPreRelease),
+        test(Version::new(1, 2, 3), Release)
+    );
+}
+
+#[test]
+fn test_increment() {
+    use VersionDiff::*;
+
+    let test = |v, d, id, base| {
+        let mut ver = Version::from(v).unwrap();
+        let opts = IncrementOptions {
+            identifier: VersionPreRelease::new(id).ok(),
+            identifier_base: base,
+        };
+
+        ver.increment(d, &opts).map(|_| ver.to_string())
+    };
+    let ok = |s: &str| Ok(s.to_string());
+
+    assert_eq!(
+        ok("1.3.0-beta.0"),
+        test("1.2.3", PreMinor, "beta", IdentifierBase::Zero)
+    );
+    assert_eq!(
+        ok("2.0.0-beta.1"),
+        test("1.2.3", PreMajor, "beta", IdentifierBase::One)
+    );
+    assert_eq!(
+        ok("1.2.4-beta"),
+        test("1.2.3", PrePatch, "beta", IdentifierBase::Omitted)
+    );
+    assert_eq!(
+        ok("1.2.4-1"),
+        test("1.2.3", PreRelease, "", IdentifierBase::One)
+    );
+    assert_eq!(
+        ok("1.2.4-beta.0"),
+        test("1.2.3", PreRelease, "beta", IdentifierBase::Zero)
+    );
+    assert_eq!(
+        ok("1.2.4-beta.0"),
+        test("1.2.4-alpha.3", PreRelease, "beta", IdentifierBase::Zero)
+    );
+    assert_eq!(
+        ok("1.2.4-beta.4"),
+        test("1.2.4-beta.3", PreRelease, "beta", IdentifierBase::Zero)
+    );
+    assert_eq!(
+        ok("1.3.0-beta.0"),
+        test("1.2.4-beta.3", PreMinor, "beta", IdentifierBase::Zero)
+    );
+    assert_eq!(
+        ok("1.2.4"),
+        test("1.2.4-beta.3", Release, "beta", IdentifierBase::Zero)
+    );
+    assert_eq!(
+        ok("2.0.0"),
+        test("1.2.3", Major, "beta", IdentifierBase::Zero)
+    );
+    assert_eq!(
+        Err(VersionIncrementError::IdentifierExists),
+        test("1.2.4-beta", PreRelease, "beta", IdentifierBase::Omitted)
+    );
+    assert_eq!(
+        Err(VersionIncrementError::MissingIdentifier),
+        test("1.2.3", PreMinor, "", IdentifierBase::Omitted)
+    );
+
+    let mut ver = Version::from("1.2.3-rc.18446744073709551615+build").unwrap();
+    assert_eq!(
+        Err(VersionIncrementError::Overflow),
+        ver.increment(PreRelease, &IncrementOptions::default())
+    );
+    assert_eq!("1.2.3-rc.18446744073709551615+build", ver.to_string());
+}
+
+#[test]
+fn test_to_upper_bound() {
+    use VersionDiff::*;
+
+    let max = u64::MAX;
+    let test = |v: Version, d| v.to_upper_bound(d).map(|v| v.to_string());
+
+    assert_eq!(
+        Some("1.3.0-0".to_string()),
+        test(Version::new(1, 2, 3), PreMinor)
+    );
+    assert_eq!(
+        Some("1.3.0-0".to_string()),
+        test(Version::new(1, 2, max), PrePatch)
+    );
+    assert_eq!(
+        Some("2.0.0-0".to_string()),
+        test(Version::new(1, max, max), PrePatch)
+    );
+    assert_eq!(
+        Some("2.0.0-0".to_string()),
+        test(Version::new(1, max, 3), PreMinor)
+    );
+    assert_eq!(None, test(Version::new(max, max, 3), PreMinor));
+    assert_eq!(None, test(Version::new(max, 0, 0), PreMajor));
 }
 
 impl Version {
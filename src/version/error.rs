@@ -0,0 +1,112 @@
+use std::{error, fmt};
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum VersionParseErrorKind {
+    LeadingZero,
+    MissingComponent,
+    EmptyIdentifier,
+    InvalidCharacter,
+    NumericOverflow,
+    TrailingInput,
+}
+
+impl fmt::Display for VersionParseErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::LeadingZero => write!(f, "numeric identifier has a leading zero"),
+            Self::MissingComponent => write!(f, "missing version component"),
+            Self::EmptyIdentifier => write!(f, "empty identifier"),
+            Self::InvalidCharacter => write!(f, "invalid character"),
+            Self::NumericOverflow => write!(f, "numeric identifier is too large"),
+            Self::TrailingInput => write!(f, "unexpected trailing input"),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct VersionParseError {
+    input: String,
+    offset: usize,
+    kind: VersionParseErrorKind,
+}
+
+impl VersionParseError {
+    /// Builds an error for `input`, which failed to parse at the position where `rest` starts.
+    pub(crate) fn new(input: &str, rest: &str, kind: VersionParseErrorKind) -> Self {
+        Self {
+            input: input.to_string(),
+            offset: input.len() - rest.len(),
+            kind,
+        }
+    }
+
+    /// The string that was being parsed.
+    pub fn input(&self) -> &str {
+        &self.input
+    }
+
+    /// Byte offset into `input` at which parsing failed.
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+
+    pub fn kind(&self) -> VersionParseErrorKind {
+        self.kind
+    }
+}
+
+impl fmt::Display for VersionParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let column = self.input[..self.offset].chars().count();
+
+        writeln!(f, "{} at offset {}", self.kind, self.offset)?;
+        writeln!(f, "  {}", self.input)?;
+        write!(f, "  {:>width$}", '^', width = column + 1)
+    }
+}
+
+impl error::Error for VersionParseError {}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum VersionIncrementError {
+    /// The incremented component (or the rightmost numeric part of the pre-release tag) is already `u64::MAX`.
+    Overflow,
+    /// A pre-release increment was requested with `IdentifierBase::Omitted` but without an identifier.
+    MissingIdentifier,
+    /// The pre-release tag wouldn't change, e.g. when `1.2.3-beta` is incremented with identifier `beta`
+    /// and `IdentifierBase::Omitted`.
+    IdentifierExists,
+    /// `VersionDiff::Release` was requested for a version that doesn't have a pre-release tag to drop.
+    MissingPreRelease,
+}
+
+impl fmt::Display for VersionIncrementError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Overflow => write!(f, "numeric identifier is too large to be incremented"),
+            Self::MissingIdentifier => write!(f, "pre-release identifier is missing"),
+            Self::IdentifierExists => write!(f, "pre-release identifier already exists"),
+            Self::MissingPreRelease => write!(f, "version doesn't have a pre-release tag"),
+        }
+    }
+}
+
+impl error::Error for VersionIncrementError {}
+
+#[test]
+fn test_to_string() {
+    use VersionParseErrorKind::*;
+
+    assert_eq!(
+        "numeric identifier has a leading zero at offset 4\n  1.2.03\n      ^",
+        VersionParseError::new("1.2.03", "03", LeadingZero).to_string()
+    );
+    assert_eq!(
+        "missing version component at offset 3\n  1.2\n     ^",
+        VersionParseError::new("1.2", "", MissingComponent).to_string()
+    );
+    assert_eq!(
+        "invalid character at offset 0\n  x\n  ^",
+        VersionParseError::new("x", "x", InvalidCharacter).to_string()
+    );
+}
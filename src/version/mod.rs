@@ -1,13 +1,20 @@
-use std::{cmp, fmt, hash};
+use std::{cmp, fmt, hash, str};
 
 use self::core::VersionCore;
 use build::VersionBuild;
+use common::ParseResult;
+use error::{VersionParseError, VersionParseErrorKind};
+use mode::ParseMode;
 use pre_release::VersionPreRelease;
 
 pub(crate) mod build;
+pub(crate) mod builder;
+pub(crate) mod coerce;
 pub(crate) mod common;
 pub(crate) mod core;
 pub(crate) mod difference;
+pub(crate) mod error;
+pub(crate) mod mode;
 pub(crate) mod pattern;
 pub(crate) mod pre_release;
 
@@ -19,7 +26,8 @@ pub struct Version {
 }
 
 impl Version {
-    pub(crate) fn new(major: u32, minor: u32, patch: u32) -> Self {
+    /// Builds a version without a pre-release tag or build metadata; see `VersionBuilder` for those.
+    pub fn new(major: u64, minor: u64, patch: u64) -> Self {
         Self {
             core: VersionCore::new(major, minor, patch),
             pre_release: None,
@@ -60,7 +68,7 @@ fn test_to_string() {
     assert_eq!(
         "1.2.3-foo",
         Version::new(1, 2, 3)
-            .with_pre_release(VersionPreRelease("foo".to_string()))
+            .with_pre_release(VersionPreRelease::new("foo").unwrap())
             .to_string()
     );
     assert_eq!(
@@ -70,7 +78,7 @@ fn test_to_string() {
     assert_eq!(
         "1.2.3-foo.bar+baz",
         with_build(
-            Version::new(1, 2, 3).with_pre_release(VersionPreRelease("foo.bar".to_string())),
+            Version::new(1, 2, 3).with_pre_release(VersionPreRelease::new("foo.bar").unwrap()),
             VersionBuild("baz".to_string())
         )
         .to_string()
@@ -177,24 +185,61 @@ impl Version {
     /// Note that it deviates from it slightly by allowing the `v` prefix which is commonly used in practice.
     ///
     /// If there are any additional (e.g. whitespace) characters around the version string, make sure
-    /// to trim them beforehand, otherwise `None` will be returned.
-    pub fn from(s: &str) -> Option<Self> {
-        let (ver, r) = Self::parse(s)?;
+    /// to trim them beforehand, otherwise an error of kind `TrailingInput` will be returned.
+    pub fn from(s: &str) -> Result<Self, VersionParseError> {
+        Self::from_mode(ParseMode::Standard, s)
+    }
+
+    /// Same as `from`, but allows choosing a stricter or a more lenient grammar (see `ParseMode`).
+    pub fn from_mode(mode: ParseMode, s: &str) -> Result<Self, VersionParseError> {
+        let (ver, r) =
+            Self::parse_with(mode, s).map_err(|(kind, r)| VersionParseError::new(s, r, kind))?;
+        let r = match mode {
+            ParseMode::Loose => r.trim_start(),
+            _ => r,
+        };
 
         if r.is_empty() {
-            Some(ver)
+            Ok(ver)
         } else {
-            None
+            Err(VersionParseError::new(
+                s,
+                r,
+                VersionParseErrorKind::TrailingInput,
+            ))
         }
     }
 
-    pub(crate) fn parse(s: &str) -> Option<(Self, &str)> {
-        let r = s.strip_prefix('v').unwrap_or(s);
-        let (core, r) = VersionCore::parse(r)?;
-        let (pre_release, r) = VersionPreRelease::parse(r)?;
+    pub(crate) fn parse(s: &str) -> ParseResult<'_, Self> {
+        Self::parse_with(ParseMode::Standard, s)
+    }
+
+    pub(crate) fn parse_with(mode: ParseMode, s: &str) -> ParseResult<'_, Self> {
+        let (core, pre_release, r) = match mode {
+            ParseMode::Strict | ParseMode::Standard => {
+                let r = match mode {
+                    ParseMode::Standard => s.strip_prefix('v').unwrap_or(s),
+                    _ => s,
+                };
+                let (core, r) = VersionCore::parse(r)?;
+                let (pre_release, r) = VersionPreRelease::parse(r)?;
+
+                (core, pre_release, r)
+            }
+            ParseMode::Loose => {
+                let r = s.trim_start_matches(|c: char| {
+                    c == '=' || c == 'v' || c == 'V' || c.is_whitespace()
+                });
+                let (core, r) = VersionCore::parse_loose(r)?;
+                let (pre_release, r) = VersionPreRelease::parse_loose(r)?;
+
+                (core, pre_release, r)
+            }
+        };
+
         let (build, r) = VersionBuild::parse(r)?;
 
-        Some((
+        Ok((
             Self {
                 core,
                 pre_release,
@@ -205,22 +250,88 @@ impl Version {
     }
 }
 
+impl str::FromStr for Version {
+    type Err = VersionParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::from(s)
+    }
+}
+
+impl TryFrom<&str> for Version {
+    type Error = VersionParseError;
+
+    fn try_from(s: &str) -> Result<Self, Self::Error> {
+        Self::from(s)
+    }
+}
+
 #[test]
 fn test_from() {
-    assert_eq!(None, Version::from("1.2.3 "));
-    assert_eq!(Some(Version::new(1, 2, 3)), Version::from("1.2.3"));
+    use VersionParseErrorKind::*;
+
+    let err = |s| Version::from(s).map_err(|e| (e.kind(), e.offset()));
+
+    assert_eq!(Err((TrailingInput, 5)), err("1.2.3 "));
+    assert_eq!(Err((TrailingInput, 5)), err("1.2.3.4"));
+    assert_eq!(Err((LeadingZero, 4)), err("1.2.03"));
+    assert_eq!(Err((MissingComponent, 3)), err("1.2"));
+    assert_eq!(Err((EmptyIdentifier, 10)), err("1.2.3-foo..bar"));
+    assert_eq!(Err((NumericOverflow, 0)), err("18446744073709551616.0.0"));
+    assert_eq!(
+        Ok(Version::new(20241017123000, 0, 0)),
+        Version::from("20241017123000.0.0")
+    );
+    assert_eq!(Err((InvalidCharacter, 6)), err("1.2.3-💩"));
+    assert_eq!(Ok(Version::new(1, 2, 3)), Version::from("1.2.3"));
+    assert_eq!(Ok(Version::new(1, 2, 3)), "1.2.3".parse());
+    assert_eq!(Ok(Version::new(1, 2, 3)), Version::try_from("v1.2.3"));
 }
 
 #[test]
 fn test_parse() {
+    use VersionParseErrorKind::*;
+
     let parse = |s| Version::parse(s).expect(s).0.to_string();
 
     assert_eq!("1.2.3", parse("v1.2.3"));
     assert_eq!("1.2.3-foo.bar.0", parse("1.2.3-foo.bar.0"));
     assert_eq!("1.2.3+foo.01", parse("1.2.3+foo.01"));
     assert_eq!("1.2.3-foo+bar", parse("1.2.3-foo+bar"));
-    assert_eq!(None, Version::parse("v"));
-    assert_eq!(None, Version::parse("-foo"));
-    assert_eq!(None, Version::parse("+foo"));
-    assert_eq!(None, Version::parse("+foo-bar"));
+    assert_eq!(Err((MissingComponent, "")), Version::parse("v"));
+    assert_eq!(Err((InvalidCharacter, "-foo")), Version::parse("-foo"));
+    assert_eq!(Err((InvalidCharacter, "+foo")), Version::parse("+foo"));
+    assert_eq!(
+        Err((InvalidCharacter, "+foo-bar")),
+        Version::parse("+foo-bar")
+    );
+}
+
+#[test]
+fn test_from_mode() {
+    use ParseMode::*;
+    use VersionParseErrorKind::*;
+
+    let parse = |m, s| Version::from_mode(m, s).expect(s).to_string();
+    let err = |m, s| Version::from_mode(m, s).map_err(|e| (e.kind(), e.offset()));
+
+    assert_eq!("1.2.3-foo", parse(Strict, "1.2.3-foo"));
+    assert_eq!(Err((InvalidCharacter, 0)), err(Strict, "v1.2.3"));
+    assert_eq!("1.2.3", parse(Standard, "v1.2.3"));
+    assert_eq!(Err((InvalidCharacter, 0)), err(Standard, "V1.2.3"));
+    assert_eq!("1.2.3", parse(Loose, "=v1.2.3"));
+    assert_eq!("1.2.3", parse(Loose, " V1.2.3 "));
+    assert_eq!("1.2.3", parse(Loose, "v1.2.3"));
+    assert_eq!("1.2.3", parse(Loose, "=1.2.3"));
+    assert_eq!("1.2.3", parse(Loose, "= v 1.2.3"));
+    assert_eq!("1.2.3", parse(Loose, "v=1.2.3"));
+    assert_eq!("1.2.3", parse(Loose, "vv1.2.3"));
+    assert_eq!("1.2.3-beta", parse(Loose, "1.2.3beta"));
+    assert_eq!(
+        "1.2.3-beta.1+build.01",
+        parse(Loose, "01.2.03-beta.01+build.01")
+    );
+    assert_eq!(Err((TrailingInput, 7)), err(Loose, " 1.2.3 x"));
+    assert_eq!(Err((MissingComponent, 5)), err(Loose, "v 1.2"));
+    assert_eq!(Err((LeadingZero, 4)), err(Standard, "1.2.03"));
 }
@@ -0,0 +1,13 @@
+/// Selects the grammar used when parsing a `Version`.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum ParseMode {
+    /// Exactly the grammar described on https://semver.org, so the `v` prefix is rejected.
+    Strict,
+    /// The grammar described on https://semver.org plus an optional `v` prefix.
+    #[default]
+    Standard,
+    /// Mirrors `node-semver`'s `loose` option: the input may be surrounded by whitespace and prefixed
+    /// with any number of `=`, `v` or `V`, the pre-release tag may omit its leading hyphen,
+    /// and numeric identifiers may have leading zeros (which are dropped).
+    Loose,
+}
@@ -1,13 +1,14 @@
 use std::fmt;
 
-use super::common::parse_num_id;
+use super::common::{parse_num_id, ParseResult};
+use super::error::VersionParseErrorKind;
 use super::Version;
 
 #[derive(Debug, PartialEq)]
 pub(crate) enum VersionPattern {
     Major,
-    Minor(u32),
-    Patch(u32, u32),
+    Minor(u64),
+    Patch(u64, u64),
 }
 
 impl fmt::Display for VersionPattern {
@@ -30,8 +31,8 @@ fn test_to_string() {
 }
 
 impl VersionPattern {
-    pub(crate) fn parse(s: &str) -> Option<(Self, &str)> {
-        let mut parts = [None::<u32>; 3];
+    pub(crate) fn parse(s: &str) -> ParseResult<'_, Self> {
+        let mut parts = [None::<u64>; 3];
         let mut r = s;
 
         for idx in 0..parts.len() {
@@ -43,66 +44,78 @@ impl VersionPattern {
                 }
             }
 
-            if let Some((p, t)) = parse_num_id(r) {
-                if idx != 0 && parts[idx - 1].is_none() {
-                    return None;
-                }
+            let err = match parse_num_id(r) {
+                Ok((p, t)) => {
+                    if idx != 0 && parts[idx - 1].is_none() {
+                        return Err((VersionParseErrorKind::InvalidCharacter, r));
+                    }
 
-                parts[idx] = Some(p);
-                r = t;
-                continue;
-            }
+                    parts[idx] = Some(p);
+                    r = t;
+                    continue;
+                }
+                Err(err) => err,
+            };
 
             if let Some(t) = r.strip_prefix(['*', 'x', 'X']) {
                 r = t;
                 continue;
             }
 
-            return None;
+            return Err(err);
         }
 
         match parts {
-            [Some(major), Some(minor), None] => Some((Self::Patch(major, minor), r)),
-            [Some(major), None, None] => Some((Self::Minor(major), r)),
-            [None, None, None] => Some((Self::Major, r)),
-            _ => None,
+            [Some(major), Some(minor), None] => Ok((Self::Patch(major, minor), r)),
+            [Some(major), None, None] => Ok((Self::Minor(major), r)),
+            [None, None, None] => Ok((Self::Major, r)),
+            _ => Err((VersionParseErrorKind::InvalidCharacter, s)),
         }
     }
 }
 
 #[test]
 fn test_parse() {
+    use VersionParseErrorKind::*;
     use VersionPattern::*;
 
-    assert_eq!(None, VersionPattern::parse(""));
-    assert_eq!(Some((Major, "")), VersionPattern::parse("*"));
-    assert_eq!(Some((Major, "")), VersionPattern::parse("x"));
-    assert_eq!(Some((Major, "")), VersionPattern::parse("X"));
-    assert_eq!(None, VersionPattern::parse("01"));
-    assert_eq!(Some((Minor(1), "")), VersionPattern::parse("1"));
-    assert_eq!(None, VersionPattern::parse("1."));
-    assert_eq!(None, VersionPattern::parse("1.?"));
-    assert_eq!(Some((Minor(1), "")), VersionPattern::parse("1.*"));
-    assert_eq!(Some((Minor(1), "*")), VersionPattern::parse("1.**"));
-    assert_eq!(Some((Minor(1), ".")), VersionPattern::parse("1.*.*."));
-    assert_eq!(Some((Patch(1, 2), "")), VersionPattern::parse("1.2"));
-    assert_eq!(Some((Patch(1, 2), "")), VersionPattern::parse("1.2.*"));
-    assert_eq!(None, VersionPattern::parse("1.2."));
-    assert_eq!(Some((Patch(1, 2), "")), VersionPattern::parse("1.2.*"));
-    assert_eq!(None, VersionPattern::parse("1.2.3"));
+    assert_eq!(Err((MissingComponent, "")), VersionPattern::parse(""));
+    assert_eq!(Ok((Major, "")), VersionPattern::parse("*"));
+    assert_eq!(Ok((Major, "")), VersionPattern::parse("x"));
+    assert_eq!(Ok((Major, "")), VersionPattern::parse("X"));
+    assert_eq!(Err((LeadingZero, "01")), VersionPattern::parse("01"));
+    assert_eq!(Ok((Minor(1), "")), VersionPattern::parse("1"));
+    assert_eq!(Err((MissingComponent, "")), VersionPattern::parse("1."));
+    assert_eq!(Err((InvalidCharacter, "?")), VersionPattern::parse("1.?"));
+    assert_eq!(Ok((Minor(1), "")), VersionPattern::parse("1.*"));
+    assert_eq!(Ok((Minor(1), "*")), VersionPattern::parse("1.**"));
+    assert_eq!(Ok((Minor(1), ".")), VersionPattern::parse("1.*.*."));
+    assert_eq!(Err((InvalidCharacter, "2")), VersionPattern::parse("1.*.2"));
+    assert_eq!(Ok((Patch(1, 2), "")), VersionPattern::parse("1.2"));
+    assert_eq!(Ok((Patch(1, 2), "")), VersionPattern::parse("1.2.*"));
+    assert_eq!(Err((MissingComponent, "")), VersionPattern::parse("1.2."));
+    assert_eq!(Ok((Patch(1, 2), "")), VersionPattern::parse("1.2.*"));
+    assert_eq!(
+        Err((InvalidCharacter, "1.2.3")),
+        VersionPattern::parse("1.2.3")
+    );
 }
 
 impl VersionPattern {
+    /// Returns the inclusive lower bound and the exclusive upper one, which is `None` for `Major`
+    /// or if the pattern covers the largest representable versions (e.g. `<u64::MAX>.*`).
     pub(crate) fn to_bounds(&self) -> (Version, Option<Version>) {
+        let next_major = |major: u64| major.checked_add(1).map(|major| Version::new(major, 0, 0));
+
         match self {
             Self::Major => (Version::new(0, 0, 0), None),
-            Self::Minor(major) => (
-                Version::new(*major, 0, 0),
-                Some(Version::new(major + 1, 0, 0)),
-            ),
+            Self::Minor(major) => (Version::new(*major, 0, 0), next_major(*major)),
             Self::Patch(major, minor) => (
                 Version::new(*major, *minor, 0),
-                Some(Version::new(*major, minor + 1, 0)),
+                match minor.checked_add(1) {
+                    Some(minor) => Some(Version::new(*major, minor, 0)),
+                    None => next_major(*major),
+                },
             ),
         }
     }
@@ -121,4 +134,23 @@ fn test_to_bounds() {
         (Version::new(1, 2, 0), Some(Version::new(1, 3, 0))),
         Patch(1, 2).to_bounds()
     );
+    assert_eq!(
+        (
+            Version::new(4294967296, 0, 0),
+            Some(Version::new(4294967297, 0, 0))
+        ),
+        Minor(4294967296).to_bounds()
+    );
+    assert_eq!(
+        (Version::new(u64::MAX, 0, 0), None),
+        Minor(u64::MAX).to_bounds()
+    );
+    assert_eq!(
+        (Version::new(1, u64::MAX, 0), Some(Version::new(2, 0, 0))),
+        Patch(1, u64::MAX).to_bounds()
+    );
+    assert_eq!(
+        (Version::new(u64::MAX, u64::MAX, 0), None),
+        Patch(u64::MAX, u64::MAX).to_bounds()
+    );
 }
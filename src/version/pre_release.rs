@@ -1,132 +1,363 @@
-use std::{cmp, fmt};
+use std::{cmp, fmt, slice};
 
-use super::common::{parse_dot_sep_list, parse_num_id};
+use super::common::{
+    parse_dot_sep_list, parse_num_id, parse_num_id_loose, parse_whole, ParseResult,
+};
+use super::difference::IdentifierBase;
+use super::error::{VersionIncrementError, VersionParseError, VersionParseErrorKind};
 
+/// A single dot-separated part of a pre-release tag.
+///
+/// Numeric identifiers (including the big ones) always have lower precedence than alphanumeric ones,
+/// and are compared by their values, as described on https://semver.org.
 #[derive(Clone, Debug, Eq, Hash, PartialEq)]
-pub struct VersionPreRelease(pub String);
-
-impl Default for VersionPreRelease {
-    fn default() -> Self {
-        Self("0".to_string())
-    }
+pub enum PreReleaseIdentifier {
+    Numeric(u64),
+    /// A numeric identifier that's too large to fit into `Numeric`, without leading zeros.
+    BigNumeric(String),
+    AlphaNumeric(String),
 }
 
-impl fmt::Display for VersionPreRelease {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "-{}", self.0)
+impl PreReleaseIdentifier {
+    /// Assumes that `s` has already been validated.
+    fn from_valid(s: &str) -> Self {
+        match parse_num_id(s) {
+            Ok((id, "")) => Self::Numeric(id),
+            _ if s.chars().all(|c| c.is_ascii_digit()) => Self::BigNumeric(s.to_string()),
+            _ => Self::AlphaNumeric(s.to_string()),
+        }
     }
 }
 
-#[test]
-fn test_to_string() {
-    assert_eq!("-foo", VersionPreRelease("foo".to_string()).to_string());
+impl Ord for PreReleaseIdentifier {
+    fn cmp(&self, other: &Self) -> cmp::Ordering {
+        use PreReleaseIdentifier::*;
+
+        let rank = |id: &Self| match id {
+            Numeric(_) => 0,
+            BigNumeric(_) => 1,
+            AlphaNumeric(_) => 2,
+        };
+
+        match (self, other) {
+            (Numeric(a), Numeric(b)) => a.cmp(b),
+            // neither has leading zeros, so the longer one is larger
+            (BigNumeric(a), BigNumeric(b)) => a.len().cmp(&b.len()).then_with(|| a.cmp(b)),
+            (AlphaNumeric(a), AlphaNumeric(b)) => a.cmp(b),
+            (a, b) => rank(a).cmp(&rank(b)),
+        }
+    }
 }
 
-impl PartialOrd for VersionPreRelease {
+impl PartialOrd for PreReleaseIdentifier {
     fn partial_cmp(&self, other: &Self) -> Option<cmp::Ordering> {
         Some(self.cmp(other))
     }
 }
 
-impl Ord for VersionPreRelease {
-    fn cmp(&self, other: &Self) -> cmp::Ordering {
-        use cmp::Ordering::*;
+impl fmt::Display for PreReleaseIdentifier {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Numeric(id) => write!(f, "{}", id),
+            Self::BigNumeric(id) | Self::AlphaNumeric(id) => write!(f, "{}", id),
+        }
+    }
+}
 
-        let parts = &self.0;
-        let other_parts = &other.0;
+#[derive(Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct VersionPreRelease(pub(crate) Vec<PreReleaseIdentifier>);
 
-        for (part, other_part) in parts.split('.').zip(other_parts.split('.')) {
-            let ord = match (parse_num_id(part), parse_num_id(other_part)) {
-                (Some((_, "")), None) => Less,
-                (None, Some((_, ""))) => Greater,
-                (Some((id, "")), Some((other_id, ""))) => id.cmp(&other_id),
-                _ => part.cmp(other_part),
-            };
+impl Default for VersionPreRelease {
+    fn default() -> Self {
+        Self(vec![PreReleaseIdentifier::Numeric(0)])
+    }
+}
 
-            if let Less | Greater = ord {
-                return ord;
-            }
-        }
+impl VersionPreRelease {
+    /// Builds a pre-release tag out of dot-separated identifiers (e.g. `rc.1`, without the leading hyphen),
+    /// validating them the same way `Version::from` does.
+    pub fn new(s: &str) -> Result<Self, VersionParseError> {
+        parse_whole(s, |s| parse_dot_sep_list(s, Self::check_id)).map(|s| Self::from_valid(&s))
+    }
 
-        parts
-            .split('.')
-            .count()
-            .cmp(&other_parts.split('.').count())
+    pub fn identifiers(&self) -> slice::Iter<'_, PreReleaseIdentifier> {
+        self.0.iter()
+    }
+
+    /// Assumes that `s` has already been validated.
+    fn from_valid(s: &str) -> Self {
+        Self(s.split('.').map(PreReleaseIdentifier::from_valid).collect())
     }
 }
 
+#[test]
+fn test_new() {
+    use PreReleaseIdentifier::*;
+    use VersionParseErrorKind::*;
+
+    let err = |s| VersionPreRelease::new(s).map_err(|e| (e.kind(), e.offset()));
+
+    assert_eq!(
+        vec![&AlphaNumeric("rc".to_string()), &Numeric(1)],
+        VersionPreRelease::new("rc.1")
+            .unwrap()
+            .identifiers()
+            .collect::<Vec<_>>()
+    );
+    assert_eq!(
+        vec![&BigNumeric("18446744073709551616".to_string())],
+        VersionPreRelease::new("18446744073709551616")
+            .unwrap()
+            .identifiers()
+            .collect::<Vec<_>>()
+    );
+    assert_eq!(Err((EmptyIdentifier, 0)), err(""));
+    assert_eq!(Err((EmptyIdentifier, 3)), err("rc..1"));
+    assert_eq!(Err((LeadingZero, 3)), err("rc.01"));
+    assert_eq!(Err((TrailingInput, 4)), err("rc.1+foo"));
+}
+
+impl fmt::Display for VersionPreRelease {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.0
+            .iter()
+            .enumerate()
+            .try_for_each(|(idx, id)| match idx {
+                0 => write!(f, "-{}", id),
+                _ => write!(f, ".{}", id),
+            })
+    }
+}
+
+#[test]
+fn test_to_string() {
+    assert_eq!("-foo", VersionPreRelease::new("foo").unwrap().to_string());
+    assert_eq!(
+        "-foo.0.bar",
+        VersionPreRelease::new("foo.0.bar").unwrap().to_string()
+    );
+}
+
 #[test]
 fn test_cmp() {
-    assert!(VersionPreRelease("alpha".to_string()) < VersionPreRelease("alpha.1".to_string()));
-    assert!(VersionPreRelease("alpha.1".to_string()) < VersionPreRelease("alpha.beta".to_string()));
-    assert!(VersionPreRelease("alpha.beta".to_string()) < VersionPreRelease("beta".to_string()));
-    assert!(VersionPreRelease("beta".to_string()) < VersionPreRelease("beta.2".to_string()));
-    assert!(VersionPreRelease("beta.2".to_string()) < VersionPreRelease("beta.11".to_string()));
-    assert!(VersionPreRelease("beta.11".to_string()) < VersionPreRelease("rc.1".to_string()));
+    let pre = |s| VersionPreRelease::new(s).unwrap();
+
+    assert!(pre("alpha") < pre("alpha.1"));
+    assert!(pre("alpha.1") < pre("alpha.beta"));
+    assert!(pre("alpha.beta") < pre("beta"));
+    assert!(pre("beta") < pre("beta.2"));
+    assert!(pre("beta.2") < pre("beta.11"));
+    assert!(pre("beta.11") < pre("rc.1"));
+    assert!(pre("1") < pre("1a"));
+    assert!(pre("999") < pre("-"));
+    assert!(pre("18446744073709551615") < pre("18446744073709551616"));
+    assert!(pre("99999999999999999999") < pre("100000000000000000000"));
+    assert!(pre("100000000000000000000") < pre("100000000000000000001"));
+    assert!(pre("100000000000000000000") < pre("-"));
+    assert!(pre("100000000000000000000") < pre("a"));
+    assert!(pre("100000000000000000000") < pre("1a"));
 }
 
 impl VersionPreRelease {
-    pub(crate) fn parse(s: &str) -> Option<(Option<Self>, &str)> {
+    fn check_id(id: &str) -> Result<(), VersionParseErrorKind> {
+        if id.len() > 1 && id.starts_with('0') && id.chars().all(|c| c.is_ascii_digit()) {
+            Err(VersionParseErrorKind::LeadingZero)
+        } else {
+            Ok(())
+        }
+    }
+
+    pub(crate) fn parse(s: &str) -> ParseResult<'_, Option<Self>> {
         if let Some(r) = s.strip_prefix('-') {
-            parse_dot_sep_list(r, |p| {
-                p.chars().any(|c| !c.is_ascii_digit()) || p.len() == 1 || !p.starts_with('0')
-            })
-            .map(|(s, r)| (Some(Self(s)), r))
+            parse_dot_sep_list(r, Self::check_id).map(|(s, r)| (Some(Self::from_valid(&s)), r))
         } else {
-            Some((None, s))
+            Ok((None, s))
         }
     }
 }
 
 #[test]
 fn test_parse() {
-    assert_eq!(Some((None, "foo")), VersionPreRelease::parse("foo"));
-    assert_eq!(Some((None, "+foo")), VersionPreRelease::parse("+foo"));
-    assert_eq!(None, VersionPreRelease::parse("-foo.01"));
+    use VersionParseErrorKind::*;
+
+    let pre = |s| VersionPreRelease::new(s).unwrap();
+
+    assert_eq!(Ok((None, "foo")), VersionPreRelease::parse("foo"));
+    assert_eq!(Ok((None, "+foo")), VersionPreRelease::parse("+foo"));
+    assert_eq!(
+        Err((LeadingZero, "01")),
+        VersionPreRelease::parse("-foo.01")
+    );
+    assert_eq!(
+        Err((EmptyIdentifier, ".bar")),
+        VersionPreRelease::parse("-foo..bar")
+    );
+    assert_eq!(Ok((Some(pre("foo")), "")), VersionPreRelease::parse("-foo"));
     assert_eq!(
-        Some((Some(VersionPreRelease("foo".to_string())), "")),
-        VersionPreRelease::parse("-foo")
+        Ok((Some(pre("0a.01a")), "")),
+        VersionPreRelease::parse("-0a.01a")
     );
 }
 
 impl VersionPreRelease {
-    pub(crate) fn to_incremented(&self) -> Self {
-        let s: &str = &self.0;
-        let dots = s
-            .char_indices()
+    /// Unlike `parse`, doesn't require the leading hyphen and strips leading zeros from numeric identifiers.
+    pub(crate) fn parse_loose(s: &str) -> ParseResult<'_, Option<Self>> {
+        let r = s.strip_prefix('-').unwrap_or(s);
+
+        if !r.starts_with(|c: char| c == '-' || c.is_ascii_alphanumeric()) {
+            return if r.len() != s.len() {
+                Err((VersionParseErrorKind::EmptyIdentifier, r))
+            } else {
+                Ok((None, s))
+            };
+        }
+
+        parse_dot_sep_list(r, |_| Ok(())).map(|(s, r)| {
+            let ids = s
+                .split('.')
+                .map(|p| match parse_num_id_loose(p) {
+                    Ok((id, "")) => PreReleaseIdentifier::Numeric(id),
+                    _ if p.chars().all(|c| c.is_ascii_digit()) => {
+                        PreReleaseIdentifier::BigNumeric(p.trim_start_matches('0').to_string())
+                    }
+                    _ => PreReleaseIdentifier::AlphaNumeric(p.to_string()),
+                })
+                .collect();
+
+            (Some(Self(ids)), r)
+        })
+    }
+}
+
+#[test]
+fn test_parse_loose() {
+    use VersionParseErrorKind::*;
+
+    let parse = |s| VersionPreRelease::parse_loose(s).map(|(p, r)| (p.map(|p| p.to_string()), r));
+
+    assert_eq!(Ok((None, "")), parse(""));
+    assert_eq!(Ok((None, "+foo")), parse("+foo"));
+    assert_eq!(Err((EmptyIdentifier, "+foo")), parse("-+foo"));
+    assert_eq!(Ok((Some("-foo".to_string()), "")), parse("foo"));
+    assert_eq!(Ok((Some("-foo".to_string()), "")), parse("-foo"));
+    assert_eq!(Ok((Some("--foo".to_string()), "")), parse("--foo"));
+    assert_eq!(
+        Ok((Some("-foo.1.0.0a".to_string()), "+bar")),
+        parse("foo.01.000.0a+bar")
+    );
+    assert_eq!(
+        Ok((Some("-100000000000000000000".to_string()), "")),
+        parse("00100000000000000000000")
+    );
+}
+
+impl VersionPreRelease {
+    /// Increments the tag `pre` (or starts a new one if it's missing) following `node-semver`'s `inc` with `identifier`
+    /// and `identifierBase`.
+    ///
+    /// The rightmost numeric part of the tag is incremented, or `base` is appended if there are no numeric parts.
+    /// If `identifier` is present and the tag doesn't already continue it with a number (e.g. `beta.3` for `beta`),
+    /// then the tag is replaced with `identifier` followed by `base`.
+    pub(crate) fn try_to_incremented(
+        pre: Option<&Self>,
+        identifier: Option<&Self>,
+        base: IdentifierBase,
+    ) -> Result<Self, VersionIncrementError> {
+        use PreReleaseIdentifier::*;
+
+        let base_id = match base {
+            IdentifierBase::Zero => Some(Numeric(0)),
+            IdentifierBase::One => Some(Numeric(1)),
+            IdentifierBase::Omitted => None,
+        };
+
+        if identifier.is_none() && base_id.is_none() {
+            return Err(VersionIncrementError::MissingIdentifier);
+        }
+
+        let mut ids = match pre {
+            Some(pre) => pre.0.clone(),
+            None => vec![],
+        };
+
+        match ids
+            .iter_mut()
             .rev()
-            .filter_map(|(idx, ch)| if ch == '.' { Some(idx) } else { None });
+            .find(|id| !matches!(id, AlphaNumeric(_)))
+        {
+            Some(Numeric(id)) => *id = id.checked_add(1).ok_or(VersionIncrementError::Overflow)?,
+            Some(_) => return Err(VersionIncrementError::Overflow),
+            None if pre.is_some() && pre == identifier && base_id.is_none() => {
+                return Err(VersionIncrementError::IdentifierExists)
+            }
+            None => ids.push(base_id.clone().unwrap_or(Numeric(0))),
+        }
+
+        if let Some(identifier) = identifier {
+            let continues = ids.starts_with(&identifier.0)
+                && matches!(
+                    ids.get(identifier.0.len()),
+                    Some(Numeric(_) | BigNumeric(_))
+                );
 
-        for dot_idx in dots {
-            if let Some((id, r)) = parse_num_id(&s[dot_idx + 1..]) {
-                if r.is_empty() || r.starts_with('.') {
-                    return Self(format!("{}{}{}", &s[..=dot_idx], id + 1, r));
-                }
+            if !continues {
+                ids = identifier.0.iter().cloned().chain(base_id).collect();
             }
         }
 
-        Self(format!("{}.0", s))
+        Ok(Self(ids))
     }
 }
 
 #[test]
-fn test_to_incremented() {
+fn test_try_to_incremented() {
+    use IdentifierBase::*;
+
+    let test = |s: &str, id: &str, base| {
+        let pre = VersionPreRelease::new(s).ok();
+        let id = VersionPreRelease::new(id).ok();
+
+        VersionPreRelease::try_to_incremented(pre.as_ref(), id.as_ref(), base)
+            .map(|p| p.to_string())
+    };
+
+    assert_eq!(Ok("-0".to_string()), test("", "", Zero));
+    assert_eq!(Ok("-1".to_string()), test("", "", One));
+    assert_eq!(Ok("-foo.0".to_string()), test("foo", "", Zero));
+    assert_eq!(Ok("-foo.1".to_string()), test("foo", "", One));
+    assert_eq!(Ok("-foo.5".to_string()), test("foo.4", "", Zero));
+    assert_eq!(Ok("-5".to_string()), test("4", "", Zero));
+    assert_eq!(Ok("-5.foo".to_string()), test("4.foo", "", Zero));
+    assert_eq!(
+        Ok("-foo.0.bar.2.0baz".to_string()),
+        test("foo.0.bar.1.0baz", "", Zero)
+    );
+    assert_eq!(Ok("-beta.0".to_string()), test("", "beta", Zero));
+    assert_eq!(Ok("-beta.1".to_string()), test("", "beta", One));
+    assert_eq!(Ok("-beta".to_string()), test("", "beta", Omitted));
+    assert_eq!(Ok("-beta.0".to_string()), test("alpha.3", "beta", Zero));
+    assert_eq!(Ok("-beta".to_string()), test("alpha.3", "beta", Omitted));
+    assert_eq!(Ok("-beta.4".to_string()), test("beta.3", "beta", Zero));
+    assert_eq!(Ok("-beta.4".to_string()), test("beta.3", "beta", Omitted));
+    assert_eq!(Ok("-beta.0".to_string()), test("beta", "beta", Zero));
+    assert_eq!(
+        Ok("-rc.pre.1".to_string()),
+        test("rc.pre.0", "rc.pre", Zero)
+    );
     assert_eq!(
-        "-foo.0",
-        VersionPreRelease("foo".to_string())
-            .to_incremented()
-            .to_string()
+        Err(VersionIncrementError::IdentifierExists),
+        test("beta", "beta", Omitted)
     );
     assert_eq!(
-        "-foo.5",
-        VersionPreRelease("foo.4".to_string())
-            .to_incremented()
-            .to_string()
+        Err(VersionIncrementError::MissingIdentifier),
+        test("beta.1", "", Omitted)
     );
     assert_eq!(
-        "-foo.0.bar.2.0baz",
-        VersionPreRelease("foo.0.bar.1.0baz".to_string())
-            .to_incremented()
-            .to_string()
-    )
+        Err(VersionIncrementError::Overflow),
+        test("foo.18446744073709551615", "", Zero)
+    );
+    assert_eq!(
+        Err(VersionIncrementError::Overflow),
+        test("foo.18446744073709551616.bar", "", Zero)
+    );
 }